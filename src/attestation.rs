@@ -0,0 +1,123 @@
+//! Compliance vouchers verified by the Ed25519 program.
+//!
+//! The attestor signs a [`Voucher`] off-chain and the contributor sends it to the
+//! Ed25519 program in the instruction right before `Contribute`. `Contribute` then
+//! reads that instruction back from the instructions sysvar instead of verifying
+//! the signature itself.
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+    sysvars::instructions::Instructions,
+};
+
+pub const ED25519_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+// [num_signatures, padding] followed by one set of offsets
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_START: usize = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+const SIGNATURE_START: usize = PUBKEY_START + 32;
+const MESSAGE_START: usize = SIGNATURE_START + 64;
+
+/// "wallet may contribute up to max_amount to fundraiser before expires_at"
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Voucher {
+    pub fundraiser: [u8; 32],
+    pub wallet: [u8; 32],
+    pub max_amount: u64,
+    pub expires_at: i64,
+    /// must grow with every voucher a wallet uses on a fundraiser
+    pub nonce: u64,
+}
+
+impl Voucher {
+    pub const LEN: usize = core::mem::size_of::<Voucher>();
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+/// Returns the voucher verified by the Ed25519 instruction just before the current one,
+/// provided it was signed by `attestor`.
+pub fn load_verified_voucher(
+    instructions_sysvar: &AccountInfo,
+    attestor: &[u8; 32],
+) -> Result<Voucher, ProgramError> {
+    let instructions = Instructions::try_from(instructions_sysvar)?;
+
+    let current_index = instructions.load_current_index() as usize;
+    if current_index == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let ed25519_ix = instructions.load_instruction_at(current_index - 1)?;
+
+    assert_eq!(
+        ed25519_ix.get_program_id(),
+        &ED25519_PROGRAM_ID,
+        "Voucher should be verified by the Ed25519 program"
+    );
+
+    let data = ed25519_ix.get_instruction_data();
+    if data.len() < MESSAGE_START + Voucher::LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // exactly one signature, with key, signature and message all inside this instruction
+    assert_eq!(data[0], 1, "Voucher should carry exactly one signature");
+    let offsets = &data[SIGNATURE_OFFSETS_START..PUBKEY_START];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    assert!(
+        read_u16(0) as usize == SIGNATURE_START
+            && read_u16(2) == u16::MAX
+            && read_u16(4) as usize == PUBKEY_START
+            && read_u16(6) == u16::MAX
+            && read_u16(8) as usize == MESSAGE_START
+            && read_u16(10) as usize == Voucher::LEN
+            && read_u16(12) == u16::MAX,
+        "Unexpected Ed25519 instruction layout"
+    );
+
+    assert_eq!(
+        &data[PUBKEY_START..SIGNATURE_START],
+        attestor,
+        "Voucher was not signed by the attestor"
+    );
+
+    bytemuck::try_pod_read_unaligned::<Voucher>(&data[MESSAGE_START..MESSAGE_START + Voucher::LEN])
+        .map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+/// Ed25519 program instruction data verifying `voucher`, in the layout
+/// [`load_verified_voucher`] expects.
+#[cfg(not(target_os = "solana"))]
+pub fn ed25519_instruction_data(
+    attestor: &[u8; 32],
+    signature: &[u8; 64],
+    voucher: &Voucher,
+) -> Vec<u8> {
+    let offsets = [
+        SIGNATURE_START as u16,
+        u16::MAX,
+        PUBKEY_START as u16,
+        u16::MAX,
+        MESSAGE_START as u16,
+        Voucher::LEN as u16,
+        u16::MAX,
+    ];
+
+    [
+        vec![1u8, 0],
+        offsets
+            .iter()
+            .flat_map(|offset| offset.to_le_bytes())
+            .collect(),
+        attestor.to_vec(),
+        signature.to_vec(),
+        voucher.to_bytes(),
+    ]
+    .concat()
+}
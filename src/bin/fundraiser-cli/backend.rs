@@ -0,0 +1,199 @@
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use litesvm::LiteSVM;
+use pinocchio_fundraising::client::PROGRAM_ID;
+use solana_account::Account;
+use solana_hash::Hash;
+use solana_pubkey::Pubkey;
+use solana_rpc_client_cli::rpc_client::RpcClient;
+use solana_signature::Signature;
+use solana_transaction::Transaction;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Where the commands send transactions and read accounts from, so a campaign
+/// can be rehearsed in-process before it runs against a cluster.
+pub trait Backend {
+    fn latest_blockhash(&mut self) -> Result<Hash>;
+
+    fn account(&mut self, address: &Pubkey) -> Result<Option<Account>>;
+
+    /// lamports an account of `len` bytes needs to be rent exempt
+    fn minimum_balance_for_rent_exemption(&mut self, len: usize) -> Result<u64>;
+
+    fn send(&mut self, transaction: &Transaction) -> Result<Signature>;
+
+    fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<()>;
+
+    /// moves the clock forward, only a rehearsal can
+    fn warp(&mut self, seconds: i64) -> Result<()>;
+}
+
+pub struct RpcBackend {
+    client: RpcClient,
+}
+
+impl RpcBackend {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: RpcClient::new(url),
+        }
+    }
+}
+
+impl Backend for RpcBackend {
+    fn latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(self.client.get_latest_blockhash()?)
+    }
+
+    fn account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())?
+            .value)
+    }
+
+    fn minimum_balance_for_rent_exemption(&mut self, len: usize) -> Result<u64> {
+        Ok(self.client.get_minimum_balance_for_rent_exemption(len)?)
+    }
+
+    fn send(&mut self, transaction: &Transaction) -> Result<Signature> {
+        Ok(self.client.send_and_confirm_transaction(transaction)?)
+    }
+
+    fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<()> {
+        let signature = self.client.request_airdrop(to, lamports)?;
+        while !self.client.confirm_transaction(&signature)? {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+        Ok(())
+    }
+
+    fn warp(&mut self, _seconds: i64) -> Result<()> {
+        Err("only a litesvm rehearsal can warp its clock".into())
+    }
+}
+
+/// An in-process litesvm whose accounts and clock are kept in a state file
+/// between runs, so every command of a rehearsal sees the ones before it.
+pub struct LiteSvmBackend {
+    svm: LiteSVM,
+    state: PathBuf,
+    /// every account a transaction or airdrop touched, the ones saved
+    touched: BTreeSet<Pubkey>,
+    /// how many blockhashes have expired, replayed on open so a durable nonce
+    /// an earlier run stored never matches the current blockhash
+    blockhashes: u64,
+}
+
+/// the clock, the expired blockhashes and the touched accounts
+type Snapshot = (i64, u64, Vec<(Pubkey, Account)>);
+
+impl LiteSvmBackend {
+    pub fn open(program: PathBuf, state: PathBuf) -> Result<Self> {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(PROGRAM_ID, &program)
+            .map_err(|err| format!("loading {}: {err:?}", program.display()))?;
+
+        let mut touched = BTreeSet::new();
+        let mut blockhashes = 0;
+        if state.exists() {
+            let (unix_timestamp, expired, accounts): Snapshot =
+                bincode::deserialize(&fs::read(&state)?)?;
+            let mut clock = svm.get_sysvar::<solana_clock::Clock>();
+            clock.unix_timestamp = unix_timestamp;
+            svm.set_sysvar(&clock);
+
+            // litesvm derives each blockhash from the one before
+            for _ in 0..expired {
+                svm.expire_blockhash();
+            }
+            blockhashes = expired;
+
+            for (address, account) in accounts {
+                svm.set_account(address, account)
+                    .map_err(|err| format!("restoring {address}: {err:?}"))?;
+                touched.insert(address);
+            }
+        }
+
+        Ok(Self {
+            svm,
+            state,
+            touched,
+            blockhashes,
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        let accounts = self
+            .touched
+            .iter()
+            .filter_map(|address| Some((*address, self.svm.get_account(address)?)))
+            // programs and sysvars come with every fresh litesvm
+            .filter(|(_, account)| {
+                !account.executable && account.owner != solana_sdk_ids::sysvar::ID
+            })
+            .collect();
+        let snapshot: Snapshot = (
+            self.svm.get_sysvar::<solana_clock::Clock>().unix_timestamp,
+            self.blockhashes,
+            accounts,
+        );
+
+        Ok(fs::write(&self.state, bincode::serialize(&snapshot)?)?)
+    }
+
+    /// moves on to a new blockhash as a cluster would, so the same transaction
+    /// can be sent again and a durable nonce can advance
+    fn expire_blockhash(&mut self) {
+        self.svm.expire_blockhash();
+        self.blockhashes += 1;
+    }
+}
+
+impl Backend for LiteSvmBackend {
+    fn latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(self.svm.latest_blockhash())
+    }
+
+    fn account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self.svm.get_account(address))
+    }
+
+    fn minimum_balance_for_rent_exemption(&mut self, len: usize) -> Result<u64> {
+        Ok(self.svm.minimum_balance_for_rent_exemption(len))
+    }
+
+    fn send(&mut self, transaction: &Transaction) -> Result<Signature> {
+        let meta = self
+            .svm
+            .send_transaction(transaction.clone())
+            .map_err(|failed| format!("{}\n{}", failed.err, failed.meta.logs.join("\n")))?;
+
+        self.touched
+            .extend(transaction.message.account_keys.iter().copied());
+        self.expire_blockhash();
+        self.save()?;
+
+        Ok(meta.signature)
+    }
+
+    fn airdrop(&mut self, to: &Pubkey, lamports: u64) -> Result<()> {
+        self.svm
+            .airdrop(to, lamports)
+            .map_err(|failed| format!("{}", failed.err))?;
+
+        self.touched.insert(*to);
+        self.expire_blockhash();
+        self.save()
+    }
+
+    fn warp(&mut self, seconds: i64) -> Result<()> {
+        let mut clock = self.svm.get_sysvar::<solana_clock::Clock>();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+
+        self.save()
+    }
+}
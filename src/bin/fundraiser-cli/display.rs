@@ -0,0 +1,164 @@
+//! Pretty-prints program accounts straight from the program's state structs.
+
+use pinocchio_fundraising::state::{
+    AccountDiscriminator, CampaignMetadata, Config, Contributor, Dispute, Fundraiser,
+    ProgramAccount, Registry, RegistryPage,
+};
+use solana_pubkey::Pubkey;
+
+use crate::backend::Result;
+
+fn key(bytes: &[u8; 32]) -> String {
+    if bytes == &[0; 32] {
+        "none".to_string()
+    } else {
+        Pubkey::new_from_array(*bytes).to_string()
+    }
+}
+
+fn u64_of(bytes: [u8; 8]) -> u64 {
+    u64::from_le_bytes(bytes)
+}
+
+fn flag(bytes: [u8; 1]) -> bool {
+    bytes[0] != 0
+}
+
+fn load<T: ProgramAccount>(data: &[u8]) -> Result<&T> {
+    T::load(data).map_err(|err| format!("not a valid account: {err:?}").into())
+}
+
+/// Prints whichever program account `data` holds, by its discriminator.
+pub fn print_account(address: &Pubkey, data: &[u8]) -> Result<()> {
+    println!("{address}");
+
+    match data.first() {
+        Some(&d) if d == AccountDiscriminator::Fundraiser as u8 => print_fundraiser(load(data)?),
+        Some(&d) if d == AccountDiscriminator::Contributor as u8 => print_contributor(load(data)?),
+        Some(&d) if d == AccountDiscriminator::CampaignMetadata as u8 => {
+            print_metadata(load(data)?)
+        }
+        Some(&d) if d == AccountDiscriminator::Config as u8 => print_config(load(data)?),
+        Some(&d) if d == AccountDiscriminator::Dispute as u8 => print_dispute(load(data)?),
+        Some(&d) if d == AccountDiscriminator::Registry as u8 => print_registry(load(data)?),
+        Some(&d) if d == AccountDiscriminator::RegistryPage as u8 => {
+            print_registry_page(load(data)?)
+        }
+        _ => println!(
+            "  {} bytes this tool does not know how to print",
+            data.len()
+        ),
+    }
+
+    Ok(())
+}
+
+fn print_fundraiser(fundraiser: &Fundraiser) {
+    println!(
+        "  kind:               fundraiser (layout v{})",
+        fundraiser.version[0]
+    );
+    println!("  maker:              {}", key(&fundraiser.maker));
+    println!("  authority:          {}", key(&fundraiser.authority));
+    println!("  mint:               {}", key(&fundraiser.mint_to_raise));
+    println!("  vault:              {}", key(&fundraiser.vault));
+    println!(
+        "  raised:             {} of {}",
+        u64_of(fundraiser.current_amount),
+        u64_of(fundraiser.amount_to_raise)
+    );
+    println!("  started at:         {}", u64_of(fundraiser.time_started));
+    println!("  deadline:           {}", fundraiser.deadline());
+    println!("  target reached:     {}", fundraiser.target_reached());
+    println!("  claimed:            {}", fundraiser.is_claimed());
+    println!("  cancelled:          {}", fundraiser.is_cancelled());
+    println!("  paused:             {}", fundraiser.is_paused());
+    println!("  disputed:           {}", fundraiser.is_disputed());
+    if fundraiser.is_timelocked() {
+        println!(
+            "  claim timelock:     {}s",
+            u64_of(fundraiser.claim_timelock)
+        );
+        if fundraiser.claim_requested() {
+            println!("  claim unlocks at:   {}", fundraiser.claim_unlocks_at());
+        }
+    }
+    if fundraiser.has_arbiter() {
+        println!("  arbiter:            {}", key(&fundraiser.arbiter));
+    }
+    if fundraiser.has_allowlist() {
+        println!("  allowlist root:     {}", key(&fundraiser.allowlist_root));
+    }
+    if fundraiser.has_attestor() {
+        println!("  attestor:           {}", key(&fundraiser.attestor));
+    }
+    if fundraiser.is_sale() {
+        println!("  allocation mint:    {}", key(&fundraiser.allocation_mint));
+    }
+    let cosigner_count = fundraiser.cosigner_count[0] as usize;
+    if cosigner_count > 0 {
+        println!(
+            "  cosigners:          {} of {}",
+            fundraiser.threshold[0], cosigner_count
+        );
+        for cosigner in &fundraiser.cosigners[..cosigner_count] {
+            println!("    {}", key(cosigner));
+        }
+    }
+    println!(
+        "  registry index:     {}",
+        u64_of(fundraiser.registry_index)
+    );
+}
+
+fn print_contributor(contributor: &Contributor) {
+    println!(
+        "  kind:               contributor (layout v{})",
+        contributor.version[0]
+    );
+    println!("  fundraiser:         {}", key(&contributor.fundraiser));
+    println!("  wallet:             {}", key(&contributor.wallet));
+    println!("  contributed:        {}", u64_of(contributor.amount));
+    println!("  objected:           {}", flag(contributor.objected));
+}
+
+fn print_metadata(metadata: &CampaignMetadata) {
+    println!("  kind:               campaign metadata");
+    println!("  fundraiser:         {}", key(&metadata.fundraiser));
+    println!("  title:              {}", metadata.title());
+    println!("  uri:                {}", metadata.uri());
+    println!("  category:           {}", metadata.category[0]);
+}
+
+fn print_config(config: &Config) {
+    println!("  kind:               config");
+    println!("  guardian:           {}", key(&config.guardian));
+    println!("  paused:             {}", config.is_paused());
+}
+
+fn print_dispute(dispute: &Dispute) {
+    println!("  kind:               dispute");
+    println!("  fundraiser:         {}", key(&dispute.fundraiser));
+    println!("  opened by:          {}", key(&dispute.opened_by));
+    println!("  reason:             {}", dispute.reason[0]);
+    println!("  resolved:           {}", dispute.is_resolved());
+}
+
+fn print_registry(registry: &Registry) {
+    println!("  kind:               registry");
+    println!("  campaigns:          {}", u64_of(registry.count));
+}
+
+fn print_registry_page(page: &RegistryPage) {
+    println!("  kind:               registry page");
+    println!("  page:               {}", u64_of(page.index));
+    println!("  entries:            {}", page.len[0]);
+    for entry in page.entries() {
+        println!(
+            "    {} category {}{}",
+            key(&entry.fundraiser),
+            entry.category[0],
+            if entry.is_closed() { " closed" } else { "" }
+        );
+    }
+}
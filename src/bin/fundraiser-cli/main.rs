@@ -0,0 +1,685 @@
+//! Command line tool for campaign operators.
+//!
+//! Every command runs against a [`Backend`]: an rpc endpoint with `--url`, or
+//! with `--rehearse <STATE>` an in-process litesvm whose accounts are kept in
+//! `STATE` between runs, to rehearse a campaign before it goes live.
+//!
+//! Admin transactions can also be built with `build`, signed on an offline
+//! machine with `sign` and sent later with `submit`, see [`offline`].
+
+mod backend;
+mod display;
+mod offline;
+#[cfg(test)]
+mod tests;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use pinocchio_fundraising::{
+    client,
+    instructions::{ContributeArgs, Extend, InitializeFundraiser, ProposeAuthority},
+    state::{ProgramAccount, Registry},
+};
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use backend::{Backend, LiteSvmBackend, Result, RpcBackend};
+use offline::Lifetime;
+
+#[derive(Parser)]
+#[command(name = "fundraiser-cli", about = "Run fundraising campaigns")]
+struct Cli {
+    /// rpc endpoint to send transactions to
+    #[arg(long, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// rehearse in an in-process litesvm, keeping its accounts in this file
+    #[arg(long, global = true, value_name = "STATE")]
+    rehearse: Option<PathBuf>,
+
+    /// the program loaded into a rehearsal
+    #[arg(
+        long,
+        global = true,
+        default_value = "target/deploy/pinocchio_fundraising.so"
+    )]
+    program: PathBuf,
+
+    /// keypair that signs and pays
+    #[arg(long, short, global = true, default_value_t = default_keypair())]
+    keypair: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start a campaign raising `amount` of `mint` for `duration` seconds
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        duration: u64,
+        /// seconds a requested claim can be challenged for
+        #[arg(long, default_value_t = 0)]
+        claim_timelock: u64,
+        #[arg(long)]
+        arbiter: Option<Pubkey>,
+        #[arg(long)]
+        title: String,
+        #[arg(long, default_value = "")]
+        uri: String,
+        /// a `Category`, 0 for other
+        #[arg(long, default_value_t = 0)]
+        category: u8,
+    },
+    /// Contribute `amount` to `maker`'s campaign
+    Contribute {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Take a contribution back from a failed or cancelled campaign
+    Refund {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Pay a successful raise out to its authority
+    Claim {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+        /// `ExecuteClaim` for a timelocked campaign
+        #[arg(long)]
+        execute: bool,
+        #[arg(long, value_delimiter = ',')]
+        cosigners: Vec<String>,
+        /// use this durable nonce account, its authority being the keypair
+        #[arg(long)]
+        nonce: Option<Pubkey>,
+    },
+    /// End a running campaign early
+    Cancel {
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long, value_delimiter = ',')]
+        cosigners: Vec<String>,
+        /// use this durable nonce account, its authority being the keypair
+        #[arg(long)]
+        nonce: Option<Pubkey>,
+    },
+    /// Create a durable nonce account, for admin transactions that take longer
+    /// to sign than a blockhash lives
+    CreateNonce {
+        /// keypair of the new nonce account
+        nonce_keypair: String,
+        /// who may advance the nonce, the keypair if not given
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Print a fundraiser, contributor or other program account, the campaign
+    /// registry if none is given
+    Inspect {
+        address: Option<Pubkey>,
+        /// inspect this maker's fundraiser
+        #[arg(long, conflicts_with = "address")]
+        maker: Option<Pubkey>,
+        /// with `--maker`, inspect this wallet's contributor ledger instead
+        #[arg(long, requires = "maker")]
+        wallet: Option<Pubkey>,
+        /// inspect this page of the campaign registry
+        #[arg(long, conflicts_with_all = ["address", "maker"])]
+        page: Option<u64>,
+    },
+    /// Create a mint with the keypair as its mint authority, for a rehearsal
+    /// to raise a token of its own
+    CreateMint {
+        /// keypair of the new mint
+        mint_keypair: String,
+        #[arg(long, default_value_t = 6)]
+        decimals: u8,
+    },
+    /// Mint `amount` of `mint` to `to`'s associated token account, creating
+    /// it if needed, the keypair being the mint authority
+    MintTo {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// the keypair if not given
+        #[arg(long)]
+        to: Option<Pubkey>,
+    },
+    /// Fund the keypair, or `to`, with lamports
+    Airdrop {
+        lamports: u64,
+        #[arg(long)]
+        to: Option<Pubkey>,
+    },
+    /// Move a rehearsal's clock forward
+    Warp { seconds: i64 },
+    /// Write an unsigned admin transaction to sign offline
+    Build {
+        /// pays the fee, and the rent of any token account the action creates
+        #[arg(long)]
+        fee_payer: Pubkey,
+        /// a recent blockhash, or with `--nonce` the nonce account's stored value
+        #[arg(long)]
+        blockhash: Hash,
+        /// a durable nonce account, so the transaction does not expire before it is signed
+        #[arg(long)]
+        nonce: Option<Pubkey>,
+        /// the nonce account's authority, the fee payer if not given
+        #[arg(long, requires = "nonce")]
+        nonce_authority: Option<Pubkey>,
+        #[arg(long)]
+        out: PathBuf,
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+    /// Add the keypair's signature to a transaction written by `build`
+    Sign { transaction: PathBuf },
+    /// Send a transaction once `sign` has added every signature
+    Submit { transaction: PathBuf },
+    /// Print a transaction written by `build` and who has signed it
+    InspectTx { transaction: PathBuf },
+}
+
+/// The admin actions `build` can write a transaction for.
+#[derive(Subcommand)]
+enum AdminAction {
+    /// Pay a successful raise out to `authority`
+    Claim {
+        #[arg(long)]
+        authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+        /// `ExecuteClaim` for a timelocked campaign
+        #[arg(long)]
+        execute: bool,
+        #[arg(long, value_delimiter = ',')]
+        cosigners: Vec<Pubkey>,
+    },
+    /// End a running campaign early
+    Cancel {
+        #[arg(long)]
+        authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long, value_delimiter = ',')]
+        cosigners: Vec<Pubkey>,
+    },
+    /// Start the timelock of a timelocked campaign's claim
+    RequestClaim {
+        #[arg(long)]
+        authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+    },
+    /// Give a campaign more time, `duration` counted from its start
+    Extend {
+        #[arg(long)]
+        authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        duration: u64,
+    },
+    /// Propose handing a campaign to `new_authority`
+    ProposeAuthority {
+        #[arg(long)]
+        authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+        #[arg(long)]
+        new_authority: Pubkey,
+    },
+    /// Take over a campaign proposed to `new_authority`
+    AcceptAuthority {
+        #[arg(long)]
+        new_authority: Pubkey,
+        #[arg(long)]
+        maker: Pubkey,
+    },
+}
+
+impl AdminAction {
+    fn instructions(self, fee_payer: &Pubkey) -> Vec<Instruction> {
+        match self {
+            AdminAction::Claim {
+                authority,
+                maker,
+                mint,
+                execute,
+                cosigners,
+            } => {
+                let claim = if execute {
+                    client::execute_claim
+                } else {
+                    client::claim
+                };
+                vec![
+                    client::create_associated_token_account(fee_payer, &authority, &mint),
+                    client::with_cosigners(claim(&authority, &maker, &mint), &cosigners),
+                ]
+            }
+            AdminAction::Cancel {
+                authority,
+                maker,
+                cosigners,
+            } => vec![client::with_cosigners(
+                client::cancel(&authority, &maker),
+                &cosigners,
+            )],
+            AdminAction::RequestClaim { authority, maker } => {
+                vec![client::request_claim(&authority, &maker)]
+            }
+            AdminAction::Extend {
+                authority,
+                maker,
+                duration,
+            } => vec![client::extend(&authority, &maker, Extend { duration })],
+            AdminAction::ProposeAuthority {
+                authority,
+                maker,
+                new_authority,
+            } => vec![client::propose_authority(
+                &authority,
+                &maker,
+                ProposeAuthority {
+                    new_authority: new_authority.to_bytes(),
+                },
+            )],
+            AdminAction::AcceptAuthority {
+                new_authority,
+                maker,
+            } => vec![client::accept_authority(&new_authority, &maker)],
+        }
+    }
+}
+
+fn default_keypair() -> String {
+    std::env::var("HOME")
+        .map(|home| format!("{home}/.config/solana/id.json"))
+        .unwrap_or_default()
+}
+
+fn read_keypair(path: &str) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| format!("reading keypair {path}: {err}").into())
+}
+
+/// signs `instructions` with `payer` and `signers` and sends them
+fn send(
+    backend: &mut dyn Backend,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[Keypair],
+) -> Result<()> {
+    send_with_nonce(backend, instructions, payer, signers, None)
+}
+
+/// same as `send`, against `nonce`'s stored value instead of a recent
+/// blockhash when given, with `payer` as its authority
+fn send_with_nonce(
+    backend: &mut dyn Backend,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[Keypair],
+    nonce: Option<Pubkey>,
+) -> Result<()> {
+    let (instructions, blockhash) = match nonce {
+        Some(nonce) => {
+            let account = backend
+                .account(&nonce)?
+                .ok_or_else(|| format!("nonce account {nonce} does not exist"))?;
+            let (authority, value) = client::decode_nonce(&account.data)
+                .ok_or_else(|| format!("{nonce} is not an initialized nonce account"))?;
+            if authority != payer.pubkey() {
+                return Err(format!("{nonce} is advanced by {authority}").into());
+            }
+
+            (
+                client::with_durable_nonce(instructions, &nonce, &authority),
+                Hash::new_from_array(value),
+            )
+        }
+        None => (instructions.to_vec(), backend.latest_blockhash()?),
+    };
+    let signers = [&[payer][..], &signers.iter().collect::<Vec<_>>()].concat();
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        blockhash,
+    );
+
+    let signature = backend.send(&transaction)?;
+    println!("{signature}");
+    Ok(())
+}
+
+/// size of an spl-token mint
+const MINT_LEN: usize = 82;
+
+/// creates `mint` with `authority` minting it, `rent` covering its rent exemption
+fn create_mint(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    decimals: u8,
+    rent: u64,
+) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        solana_system_interface::instruction::create_account(
+            payer,
+            mint,
+            rent,
+            MINT_LEN as u64,
+            &client::TOKEN_PROGRAM_ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &client::TOKEN_PROGRAM_ID,
+            mint,
+            authority,
+            None,
+            decimals,
+        )?,
+    ])
+}
+
+/// mints `amount` to `owner`'s associated token account, creating it if needed
+fn mint_to(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        client::create_associated_token_account(authority, owner, mint),
+        spl_token::instruction::mint_to(
+            &client::TOKEN_PROGRAM_ID,
+            mint,
+            &client::associated_token_address(owner, mint),
+            authority,
+            &[],
+            amount,
+        )?,
+    ])
+}
+
+/// sends a transaction written by `build` once every signer has signed it
+fn submit(backend: &mut dyn Backend, transaction: &Transaction) -> Result<Signature> {
+    let missing = offline::missing_signers(transaction);
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(Pubkey::to_string).collect();
+        return Err(format!("still to sign: {}", missing.join(", ")).into());
+    }
+
+    backend.send(transaction)
+}
+
+/// the number of campaigns registered so far, which picks the new one's registry page
+fn registry_count(backend: &mut dyn Backend) -> Result<u64> {
+    let Some(registry) = backend.account(&client::registry_pda())? else {
+        return Ok(0);
+    };
+    let registry = Registry::load(&registry.data).map_err(|err| format!("{err:?}"))?;
+
+    Ok(u64::from_le_bytes(registry.count))
+}
+
+/// the commands that never touch the network, so they run on an offline machine
+fn run_offline(command: Command, keypair: &str) -> Result<Option<Command>> {
+    match command {
+        Command::Build {
+            fee_payer,
+            blockhash,
+            nonce,
+            nonce_authority,
+            out,
+            action,
+        } => {
+            let lifetime = match nonce {
+                Some(account) => Lifetime::Nonce {
+                    account,
+                    authority: nonce_authority.unwrap_or(fee_payer),
+                    value: blockhash,
+                },
+                None => Lifetime::Blockhash(blockhash),
+            };
+            let transaction =
+                offline::build(&action.instructions(&fee_payer), &fee_payer, lifetime);
+
+            offline::write(&out, &transaction)?;
+            offline::print(&transaction);
+        }
+        Command::Sign { transaction: path } => {
+            let mut transaction = offline::read(&path)?;
+            offline::sign(&mut transaction, &read_keypair(keypair)?)?;
+
+            offline::write(&path, &transaction)?;
+            offline::print(&transaction);
+        }
+        Command::InspectTx { transaction } => offline::print(&offline::read(&transaction)?),
+        command => return Ok(Some(command)),
+    }
+
+    Ok(None)
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let Some(command) = run_offline(cli.command, &cli.keypair)? else {
+        return Ok(());
+    };
+
+    let mut backend: Box<dyn Backend> = match cli.rehearse {
+        Some(state) => Box::new(LiteSvmBackend::open(cli.program, state)?),
+        None => Box::new(RpcBackend::new(cli.url)),
+    };
+    let backend = backend.as_mut();
+
+    match command {
+        Command::Create {
+            mint,
+            amount,
+            duration,
+            claim_timelock,
+            arbiter,
+            title,
+            uri,
+            category,
+        } => {
+            let maker = read_keypair(&cli.keypair)?;
+            let fundraiser = client::fundraiser_pda(&maker.pubkey());
+            let metadata = client::metadata(&title, &uri, category, [0; 32])
+                .ok_or("title must be 1 to 64 bytes and uri at most 128")?;
+            let args = InitializeFundraiser {
+                amount_to_raise: amount,
+                duration,
+                claim_timelock,
+                arbiter: arbiter
+                    .map(|arbiter| arbiter.to_bytes())
+                    .unwrap_or_default(),
+            };
+
+            let instructions = [
+                client::create_associated_token_account(&maker.pubkey(), &fundraiser, &mint),
+                client::initialize(
+                    &maker.pubkey(),
+                    &mint,
+                    registry_count(backend)?,
+                    args,
+                    metadata,
+                ),
+            ];
+            send(backend, &instructions, &maker, &[])?;
+            println!("fundraiser {fundraiser}");
+        }
+        Command::Contribute {
+            maker,
+            mint,
+            amount,
+        } => {
+            let contributor = read_keypair(&cli.keypair)?;
+            let instruction = client::contribute(
+                &contributor.pubkey(),
+                &maker,
+                &mint,
+                ContributeArgs { amount },
+            );
+            send(backend, &[instruction], &contributor, &[])?;
+        }
+        Command::Refund { maker, mint } => {
+            let contributor = read_keypair(&cli.keypair)?;
+            let instruction = client::refund(&contributor.pubkey(), &maker, &mint);
+            send(backend, &[instruction], &contributor, &[])?;
+        }
+        Command::Claim {
+            maker,
+            mint,
+            execute,
+            cosigners,
+            nonce,
+        } => {
+            let authority = read_keypair(&cli.keypair)?;
+            let cosigners = cosigners
+                .iter()
+                .map(|path| read_keypair(path))
+                .collect::<Result<Vec<_>>>()?;
+            let claim = if execute {
+                client::execute_claim
+            } else {
+                client::claim
+            };
+
+            let instructions = [
+                client::create_associated_token_account(
+                    &authority.pubkey(),
+                    &authority.pubkey(),
+                    &mint,
+                ),
+                client::with_cosigners(
+                    claim(&authority.pubkey(), &maker, &mint),
+                    &cosigners.iter().map(Keypair::pubkey).collect::<Vec<_>>(),
+                ),
+            ];
+            send_with_nonce(backend, &instructions, &authority, &cosigners, nonce)?;
+        }
+        Command::Cancel {
+            maker,
+            cosigners,
+            nonce,
+        } => {
+            let authority = read_keypair(&cli.keypair)?;
+            let cosigners = cosigners
+                .iter()
+                .map(|path| read_keypair(path))
+                .collect::<Result<Vec<_>>>()?;
+
+            let instruction = client::with_cosigners(
+                client::cancel(&authority.pubkey(), &maker),
+                &cosigners.iter().map(Keypair::pubkey).collect::<Vec<_>>(),
+            );
+            send_with_nonce(backend, &[instruction], &authority, &cosigners, nonce)?;
+        }
+        Command::CreateNonce {
+            nonce_keypair,
+            authority,
+        } => {
+            let payer = read_keypair(&cli.keypair)?;
+            let nonce = read_keypair(&nonce_keypair)?;
+            let rent = backend.minimum_balance_for_rent_exemption(client::NONCE_ACCOUNT_LEN)?;
+
+            let instructions = client::create_nonce_account(
+                &payer.pubkey(),
+                &nonce.pubkey(),
+                &authority.unwrap_or(payer.pubkey()),
+                rent,
+            );
+            send(backend, &instructions, &payer, &[nonce])?;
+        }
+        Command::Inspect {
+            address,
+            maker,
+            wallet,
+            page,
+        } => {
+            let address = match (address, maker, wallet, page) {
+                (Some(address), _, _, _) => address,
+                (None, Some(maker), None, _) => client::fundraiser_pda(&maker),
+                (None, Some(maker), Some(wallet), _) => {
+                    client::contributor_pda(&client::fundraiser_pda(&maker), &wallet)
+                }
+                (None, None, _, Some(page)) => client::registry_page_pda(page),
+                (None, None, _, None) => client::registry_pda(),
+            };
+            let account = backend
+                .account(&address)?
+                .ok_or_else(|| format!("{address} does not exist"))?;
+
+            display::print_account(&address, &account.data)?;
+        }
+        Command::CreateMint {
+            mint_keypair,
+            decimals,
+        } => {
+            let payer = read_keypair(&cli.keypair)?;
+            let mint = read_keypair(&mint_keypair)?;
+            let rent = backend.minimum_balance_for_rent_exemption(MINT_LEN)?;
+
+            let address = mint.pubkey();
+
+            let instructions =
+                create_mint(&payer.pubkey(), &address, &payer.pubkey(), decimals, rent)?;
+            send(backend, &instructions, &payer, &[mint])?;
+            println!("mint {address}");
+        }
+        Command::MintTo { mint, amount, to } => {
+            let authority = read_keypair(&cli.keypair)?;
+            let to = to.unwrap_or(authority.pubkey());
+
+            let instructions = mint_to(&authority.pubkey(), &mint, &to, amount)?;
+            send(backend, &instructions, &authority, &[])?;
+        }
+        Command::Airdrop { lamports, to } => {
+            let to = match to {
+                Some(to) => to,
+                None => read_keypair(&cli.keypair)?.pubkey(),
+            };
+            backend.airdrop(&to, lamports)?;
+        }
+        Command::Warp { seconds } => backend.warp(seconds)?,
+        Command::Submit { transaction } => {
+            println!("{}", submit(backend, &offline::read(&transaction)?)?);
+        }
+        Command::Build { .. } | Command::Sign { .. } | Command::InspectTx { .. } => {
+            unreachable!("handled by run_offline")
+        }
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
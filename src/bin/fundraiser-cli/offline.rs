@@ -0,0 +1,169 @@
+//! Transactions built and signed away from the network, for an air-gapped
+//! treasury: `build` writes an unsigned transaction, `sign` adds signatures
+//! from keypair files on the offline machine and `submit` sends it from an
+//! online one. Files hold the bincode transaction in base64.
+
+use std::{fs, path::Path};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use pinocchio_fundraising::{client, instructions::FundraisingInstructions};
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::backend::Result;
+
+/// What a transaction's signatures commit to instead of a recent blockhash.
+pub enum Lifetime {
+    /// a recent blockhash, which expires after about a minute
+    Blockhash(Hash),
+    /// the value stored in a durable nonce account, advanced as the first instruction
+    Nonce {
+        account: Pubkey,
+        authority: Pubkey,
+        value: Hash,
+    },
+}
+
+/// An unsigned transaction of `instructions`, paid for by `fee_payer`.
+pub fn build(instructions: &[Instruction], fee_payer: &Pubkey, lifetime: Lifetime) -> Transaction {
+    let message = match lifetime {
+        Lifetime::Blockhash(blockhash) => {
+            Message::new_with_blockhash(instructions, Some(fee_payer), &blockhash)
+        }
+        Lifetime::Nonce {
+            account,
+            authority,
+            value,
+        } => Message::new_with_blockhash(
+            &client::with_durable_nonce(instructions, &account, &authority),
+            Some(fee_payer),
+            &value,
+        ),
+    };
+
+    Transaction::new_unsigned(message)
+}
+
+/// Adds `keypair`'s signature, which must be one the transaction asks for.
+pub fn sign(transaction: &mut Transaction, keypair: &Keypair) -> Result<()> {
+    let blockhash = transaction.message.recent_blockhash;
+    transaction
+        .try_partial_sign(&[keypair], blockhash)
+        .map_err(|err| format!("{} cannot sign this transaction: {err}", keypair.pubkey()))?;
+
+    Ok(())
+}
+
+pub fn read(path: &Path) -> Result<Transaction> {
+    let encoded = fs::read_to_string(path)?;
+    let bytes = STANDARD.decode(encoded.trim())?;
+
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+pub fn write(path: &Path, transaction: &Transaction) -> Result<()> {
+    Ok(fs::write(
+        path,
+        STANDARD.encode(bincode::serialize(transaction)?),
+    )?)
+}
+
+/// signers still to sign, in the order the message lists them
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    let signers = transaction.message.header.num_required_signatures as usize;
+
+    transaction.message.account_keys[..signers]
+        .iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Default::default())
+        .map(|(signer, _)| *signer)
+        .collect()
+}
+
+fn is_advance_nonce(program_id: &Pubkey, data: &[u8]) -> bool {
+    // `AdvanceNonceAccount`
+    *program_id == client::SYSTEM_PROGRAM_ID && data == 4u32.to_le_bytes()
+}
+
+fn instruction_name(program_id: &Pubkey, data: &[u8]) -> String {
+    use FundraisingInstructions as Ix;
+
+    if is_advance_nonce(program_id, data) {
+        return "advance nonce".to_string();
+    }
+    if *program_id == client::ASSOCIATED_TOKEN_PROGRAM_ID {
+        return "create associated token account".to_string();
+    }
+    if *program_id != client::PROGRAM_ID {
+        return format!("{program_id} instruction");
+    }
+
+    let name = match data.first().map(FundraisingInstructions::try_from) {
+        Some(Ok(Ix::Claim)) => "claim",
+        Some(Ok(Ix::ExecuteClaim)) => "execute claim",
+        Some(Ok(Ix::RequestClaim)) => "request claim",
+        Some(Ok(Ix::Cancel)) => "cancel",
+        Some(Ok(Ix::Extend)) => "extend",
+        Some(Ok(Ix::ProposeAuthority)) => "propose authority",
+        Some(Ok(Ix::AcceptAuthority)) => "accept authority",
+        Some(Ok(_)) => return format!("fundraiser instruction {}", data[0]),
+        _ => "unknown fundraiser instruction",
+    };
+    name.to_string()
+}
+
+/// Prints what the transaction does and who has signed it, to check before signing.
+pub fn print(transaction: &Transaction) {
+    let message = &transaction.message;
+    let keys = &message.account_keys;
+
+    println!("fee payer:  {}", keys[0]);
+    match message.instructions.first() {
+        Some(first) if is_advance_nonce(&keys[first.program_id_index as usize], &first.data) => {
+            println!(
+                "nonce:      {} in {}",
+                message.recent_blockhash, keys[first.accounts[0] as usize]
+            )
+        }
+        _ => println!("blockhash:  {}", message.recent_blockhash),
+    }
+
+    for (position, instruction) in message.instructions.iter().enumerate() {
+        let program_id = &keys[instruction.program_id_index as usize];
+        println!(
+            "#{position} {}",
+            instruction_name(program_id, &instruction.data)
+        );
+        for index in instruction.accounts.iter().map(|index| *index as usize) {
+            println!(
+                "    {}{}{}",
+                keys[index],
+                if message.is_signer(index) {
+                    " signer"
+                } else {
+                    ""
+                },
+                if message.is_maybe_writable(index, None) {
+                    " writable"
+                } else {
+                    ""
+                },
+            );
+        }
+    }
+
+    let missing = missing_signers(transaction);
+    for signer in &keys[..message.header.num_required_signatures as usize] {
+        let status = if missing.contains(signer) {
+            "missing"
+        } else {
+            "signed"
+        };
+        println!("signature  {signer} {status}");
+    }
+}
@@ -0,0 +1,323 @@
+//! Rehearsals against the program `cargo build-sbf` wrote to [`PROGRAM_PATH`].
+
+use std::{fs, path::PathBuf};
+
+use pinocchio_fundraising::{
+    client,
+    instructions::{ContributeArgs, InitializeFundraiser},
+    state::{Fundraiser, ProgramAccount, RegistryPage},
+};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+use crate::{
+    backend::{Backend, LiteSvmBackend},
+    create_mint, display, mint_to,
+    offline::{self, Lifetime},
+    registry_count, send, submit, AdminAction, MINT_LEN,
+};
+
+const PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/target/deploy/pinocchio_fundraising.so"
+);
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// a state file of its own for the test called `name`, empty to start with
+fn state_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "fundraiser-cli-{name}-{}.state",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn open(state: &PathBuf) -> LiteSvmBackend {
+    LiteSvmBackend::open(PROGRAM_PATH.into(), state.clone()).unwrap()
+}
+
+/// `Clock::unix_timestamp`, the last of its fields
+fn unix_timestamp(backend: &mut dyn Backend) -> i64 {
+    let clock = backend
+        .account(&solana_sdk_ids::sysvar::clock::ID)
+        .unwrap()
+        .unwrap();
+    i64::from_le_bytes(clock.data[32..40].try_into().unwrap())
+}
+
+/// the `amount` of `owner`'s associated token account
+fn token_amount(backend: &mut dyn Backend, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let account = backend
+        .account(&client::associated_token_address(owner, mint))
+        .unwrap()
+        .unwrap();
+    u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+}
+
+fn current_amount(backend: &mut dyn Backend, maker: &Pubkey) -> u64 {
+    let fundraiser = backend
+        .account(&client::fundraiser_pda(maker))
+        .unwrap()
+        .unwrap();
+    u64::from_le_bytes(Fundraiser::load(&fundraiser.data).unwrap().current_amount)
+}
+
+/// funds `maker` and starts its campaign raising `mint`, which it creates and
+/// mints to `contributor`
+fn create_campaign(
+    backend: &mut dyn Backend,
+    maker: &Keypair,
+    mint: Keypair,
+    contributor: &Pubkey,
+) {
+    let mint_address = mint.pubkey();
+    backend
+        .airdrop(&maker.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+
+    let rent = backend
+        .minimum_balance_for_rent_exemption(MINT_LEN)
+        .unwrap();
+    let instructions =
+        create_mint(&maker.pubkey(), &mint_address, &maker.pubkey(), 6, rent).unwrap();
+    send(backend, &instructions, maker, &[mint]).unwrap();
+    let instructions = mint_to(&maker.pubkey(), &mint_address, contributor, 1_000_000_000).unwrap();
+    send(backend, &instructions, maker, &[]).unwrap();
+
+    let fundraiser = client::fundraiser_pda(&maker.pubkey());
+    let instructions = [
+        client::create_associated_token_account(&maker.pubkey(), &fundraiser, &mint_address),
+        client::initialize(
+            &maker.pubkey(),
+            &mint_address,
+            registry_count(backend).unwrap(),
+            InitializeFundraiser {
+                amount_to_raise: 400_000_000,
+                duration: 86_400,
+                claim_timelock: 0,
+                arbiter: [0; 32],
+            },
+            client::metadata("Clean water", "", 1, [0; 32]).unwrap(),
+        ),
+    ];
+    send(backend, &instructions, maker, &[]).unwrap();
+}
+
+#[test]
+fn test_rehearsal_survives_reopening() {
+    let state = state_file("reopen");
+    let maker = Keypair::new();
+    let contributor = Keypair::new();
+    let mint = Keypair::new();
+    let mint_address = mint.pubkey();
+
+    let mut backend = open(&state);
+    backend
+        .airdrop(&contributor.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+    create_campaign(&mut backend, &maker, mint, &contributor.pubkey());
+    let fundraiser = client::fundraiser_pda(&maker.pubkey());
+    backend.warp(3_600).unwrap();
+
+    let started = unix_timestamp(&mut backend);
+    let created = backend.account(&fundraiser).unwrap().unwrap();
+    drop(backend);
+
+    // a new run restores the accounts and the clock the last one left
+    let mut backend = open(&state);
+    assert_eq!(unix_timestamp(&mut backend), started);
+    assert_eq!(backend.account(&fundraiser).unwrap().unwrap(), created);
+    assert_eq!(registry_count(&mut backend).unwrap(), 1);
+    assert_eq!(
+        token_amount(&mut backend, &contributor.pubkey(), &mint_address),
+        1_000_000_000
+    );
+
+    let instruction = client::contribute(
+        &contributor.pubkey(),
+        &maker.pubkey(),
+        &mint_address,
+        ContributeArgs { amount: 10_000_000 },
+    );
+    send(&mut backend, &[instruction], &contributor, &[]).unwrap();
+    drop(backend);
+
+    // and builds on it, the contribution and the ledger it created are kept too
+    let mut backend = open(&state);
+    assert_eq!(current_amount(&mut backend, &maker.pubkey()), 10_000_000);
+    assert_eq!(
+        token_amount(&mut backend, &contributor.pubkey(), &mint_address),
+        990_000_000
+    );
+    assert!(backend
+        .account(&client::contributor_pda(&fundraiser, &contributor.pubkey()))
+        .unwrap()
+        .is_some());
+
+    let registry = backend.account(&client::registry_pda()).unwrap().unwrap();
+    display::print_account(&client::registry_pda(), &registry.data).unwrap();
+    let page = backend
+        .account(&client::registry_page_pda(0))
+        .unwrap()
+        .unwrap();
+    display::print_account(&client::registry_page_pda(0), &page.data).unwrap();
+    let entries = RegistryPage::load(&page.data).unwrap().entries().to_vec();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].fundraiser, fundraiser.to_bytes());
+
+    fs::remove_file(&state).unwrap();
+}
+
+#[test]
+fn test_rehearsal_without_state_starts_fresh() {
+    let state = state_file("fresh");
+    let wallet = Keypair::new();
+
+    let mut backend = open(&state);
+    assert!(!state.exists());
+    backend.airdrop(&wallet.pubkey(), LAMPORTS_PER_SOL).unwrap();
+    assert!(state.exists());
+    drop(backend);
+
+    assert_eq!(
+        open(&state)
+            .account(&wallet.pubkey())
+            .unwrap()
+            .unwrap()
+            .lamports,
+        LAMPORTS_PER_SOL
+    );
+
+    // another state file holds another rehearsal
+    let other = state_file("fresh-other");
+    assert!(open(&other).account(&wallet.pubkey()).unwrap().is_none());
+
+    fs::remove_file(&state).unwrap();
+}
+
+/// the value `nonce` holds, a built transaction's blockhash
+fn nonce_value(backend: &mut dyn Backend, nonce: &Pubkey) -> Hash {
+    let account = backend.account(nonce).unwrap().unwrap();
+    Hash::new_from_array(client::decode_nonce(&account.data).unwrap().1)
+}
+
+#[test]
+fn test_offline_transaction_round_trip() {
+    let state = state_file("offline");
+    let maker = Keypair::new();
+    let fee_payer = Keypair::new();
+    let nonce = Keypair::new();
+    let nonce_address = nonce.pubkey();
+
+    let mut backend = open(&state);
+    create_campaign(&mut backend, &maker, Keypair::new(), &maker.pubkey());
+    backend
+        .airdrop(&fee_payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+    let rent = backend
+        .minimum_balance_for_rent_exemption(client::NONCE_ACCOUNT_LEN)
+        .unwrap();
+    let instructions = client::create_nonce_account(
+        &fee_payer.pubkey(),
+        &nonce_address,
+        &fee_payer.pubkey(),
+        rent,
+    );
+    send(&mut backend, &instructions, &fee_payer, &[nonce]).unwrap();
+    drop(backend);
+
+    // built and signed while a later run of the tool has the nonce
+    let mut backend = open(&state);
+    let value = nonce_value(&mut backend, &nonce_address);
+    let lifetime = || Lifetime::Nonce {
+        account: nonce_address,
+        authority: fee_payer.pubkey(),
+        value,
+    };
+    let build = |action: AdminAction| {
+        offline::build(
+            &action.instructions(&fee_payer.pubkey()),
+            &fee_payer.pubkey(),
+            lifetime(),
+        )
+    };
+
+    let path = state.with_extension("tx");
+    offline::write(
+        &path,
+        &build(AdminAction::Cancel {
+            authority: maker.pubkey(),
+            maker: maker.pubkey(),
+            cosigners: vec![],
+        }),
+    )
+    .unwrap();
+    let mut cancel = offline::read(&path).unwrap();
+    assert_eq!(
+        offline::missing_signers(&cancel),
+        [fee_payer.pubkey(), maker.pubkey()]
+    );
+
+    // the authority signs on its own machine, the fee payer has yet to
+    offline::sign(&mut cancel, &maker).unwrap();
+    assert_eq!(offline::missing_signers(&cancel), [fee_payer.pubkey()]);
+    assert!(submit(&mut backend, &cancel)
+        .unwrap_err()
+        .to_string()
+        .starts_with("still to sign"));
+
+    // a keypair the transaction does not ask for cannot sign it
+    assert!(offline::sign(&mut cancel, &Keypair::new()).is_err());
+    assert_eq!(offline::missing_signers(&cancel), [fee_payer.pubkey()]);
+
+    // and one it asks for cannot stand in for the campaign's authority, sent
+    // against a blockhash as a failed transaction would still use up the nonce
+    let impostor = Keypair::new();
+    let action = AdminAction::Cancel {
+        authority: impostor.pubkey(),
+        maker: maker.pubkey(),
+        cosigners: vec![],
+    };
+    let mut takeover = offline::build(
+        &action.instructions(&fee_payer.pubkey()),
+        &fee_payer.pubkey(),
+        Lifetime::Blockhash(backend.latest_blockhash().unwrap()),
+    );
+    offline::sign(&mut takeover, &impostor).unwrap();
+    offline::sign(&mut takeover, &fee_payer).unwrap();
+    assert!(submit(&mut backend, &takeover).is_err());
+
+    // signed against the same nonce, so only one of the two can land
+    let mut extend = build(AdminAction::Extend {
+        authority: maker.pubkey(),
+        maker: maker.pubkey(),
+        duration: 2 * 86_400,
+    });
+    offline::sign(&mut extend, &maker).unwrap();
+    offline::sign(&mut extend, &fee_payer).unwrap();
+
+    offline::write(&path, &cancel).unwrap();
+    let mut cancel = offline::read(&path).unwrap();
+    offline::sign(&mut cancel, &fee_payer).unwrap();
+    assert!(offline::missing_signers(&cancel).is_empty());
+    submit(&mut backend, &cancel).unwrap();
+
+    let fundraiser = backend
+        .account(&client::fundraiser_pda(&maker.pubkey()))
+        .unwrap()
+        .unwrap();
+    assert!(Fundraiser::load(&fundraiser.data).unwrap().is_cancelled());
+    // sending it advanced the nonce, which leaves the other one stale
+    assert_ne!(nonce_value(&mut backend, &nonce_address), value);
+    assert!(submit(&mut backend, &extend)
+        .unwrap_err()
+        .to_string()
+        .contains("Blockhash not found"));
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&state).unwrap();
+}
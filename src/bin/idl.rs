@@ -0,0 +1,6 @@
+//! Prints the program's IDL, see `pinocchio_fundraising::idl`.
+
+fn main() {
+    let idl = pinocchio_fundraising::idl::idl();
+    println!("{}", serde_json::to_string_pretty(&idl).unwrap());
+}
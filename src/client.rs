@@ -0,0 +1,815 @@
+//! Off-chain helpers, behind the `client` feature.
+//!
+//! The builders fill the same `*Accounts` structs the processors parse, so an
+//! instruction built here always lists its accounts in the order the program
+//! reads them.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    instructions::{
+        AcceptAuthorityAccounts, AcceptAuthorityArgs, AllowlistArgs, CancelAccounts, CancelArgs,
+        ClaimAccounts, ClaimAllocationAccounts, ClaimAllocationArgs, ClaimArgs, ContributeAccounts,
+        ContributeArgs, DelistAccounts, DelistArgs, Extend, ExtendAccounts,
+        FundraisingInstructions, InitializeAccounts, InitializeConfigAccounts,
+        InitializeConfigArgs, InitializeFundraiser, InitializeSale, InitializeSaleAccounts,
+        MigrateAccounts, MigrateArgs, ObjectClaimAccounts, ObjectClaimArgs, OpenDispute,
+        OpenDisputeAccounts, ProposeAuthority, ProposeAuthorityAccounts, ReclaimAllocationAccounts,
+        ReclaimAllocationArgs, RefundAccounts, RefundArgs, RequestClaimAccounts, RequestClaimArgs,
+        ResolveDispute, ResolveDisputeAccounts, SetAllowlist, SetAllowlistAccounts, SetAttestor,
+        SetAttestorAccounts, SetCosigners, SetCosignersAccounts, SetGuardianAccounts,
+        SetGuardianArgs, SetPause, SetPauseAccounts, SettleAccounts, SettleArgs, UpdateMetadata,
+        UpdateMetadataAccounts,
+    },
+    state::{
+        registry_position, Contributor, Fundraiser, ProgramAccount, MAX_TITLE_LEN, MAX_URI_LEN,
+    },
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+pub const SYSTEM_PROGRAM_ID: Pubkey = solana_pubkey::pubkey!("11111111111111111111111111111111");
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const RENT_SYSVAR_ID: Pubkey =
+    solana_pubkey::pubkey!("SysvarRent111111111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey =
+    solana_pubkey::pubkey!("Sysvar1nstructions1111111111111111111111111");
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    Pubkey::new_from_array(crate::instructions::BPF_LOADER_UPGRADEABLE_ID);
+
+/// size of a system program nonce account
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+
+pub fn fundraiser_pda(maker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fundraiser", maker.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn contributor_pda(fundraiser: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"contributor", fundraiser.as_ref(), wallet.as_ref()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).0
+}
+
+pub fn metadata_pda(fundraiser: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", fundraiser.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn dispute_pda(fundraiser: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"dispute", fundraiser.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn registry_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"registry"], &PROGRAM_ID).0
+}
+
+pub fn registry_page_pda(page_index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"registry", &page_index.to_le_bytes()], &PROGRAM_ID).0
+}
+
+pub fn event_authority_pda() -> Pubkey {
+    Pubkey::new_from_array(crate::events::EVENT_AUTHORITY)
+}
+
+/// the upgradeable loader account holding this program's upgrade authority
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID).0
+}
+
+/// associated token account of `owner` for `mint`, which is also how vaults are derived
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn decode_fundraiser(data: &[u8]) -> Option<Fundraiser> {
+    Fundraiser::load(data).ok().copied()
+}
+
+pub fn decode_contributor(data: &[u8]) -> Option<Contributor> {
+    Contributor::load(data).ok().copied()
+}
+
+/// Metadata for [`initialize`], `None` if `title` is empty or either string is too long.
+pub fn metadata(
+    title: &str,
+    uri: &str,
+    category: u8,
+    image_hash: [u8; 32],
+) -> Option<UpdateMetadata> {
+    if title.is_empty() || title.len() > MAX_TITLE_LEN || uri.len() > MAX_URI_LEN {
+        return None;
+    }
+
+    let mut metadata = UpdateMetadata {
+        title_len: title.len() as u8,
+        title: [0; MAX_TITLE_LEN],
+        uri_len: uri.len() as u8,
+        uri: [0; MAX_URI_LEN],
+        category,
+        image_hash,
+    };
+    metadata.title[..title.len()].copy_from_slice(title.as_bytes());
+    metadata.uri[..uri.len()].copy_from_slice(uri.as_bytes());
+
+    Some(metadata)
+}
+
+/// Creates `owner`'s associated token account for `mint` unless it exists,
+/// as `initialize` needs the vault and `claim` the authority's account to.
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        // `CreateIdempotent`
+        data: vec![1],
+    }
+}
+
+fn instruction(
+    discriminator: FundraisingInstructions,
+    accounts: Vec<AccountMeta>,
+    args: &[u8],
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: [&[discriminator as u8][..], args].concat(),
+    }
+}
+
+/// `registry_count` is the `count` of the `Registry` account, 0 before the first campaign.
+pub fn initialize(
+    maker: &Pubkey,
+    mint: &Pubkey,
+    registry_count: u64,
+    args: InitializeFundraiser,
+    metadata: UpdateMetadata,
+) -> Instruction {
+    instruction(
+        FundraisingInstructions::Initialize,
+        initialize_accounts(maker, mint, registry_count).to_vec(),
+        &[args.to_bytes(), metadata.to_bytes()].concat(),
+    )
+}
+
+/// `InitializeSale`, handing out `allocation_mint` from the fundraiser's
+/// associated token account, which the maker funds from theirs.
+pub fn initialize_sale(
+    maker: &Pubkey,
+    mint: &Pubkey,
+    allocation_mint: &Pubkey,
+    registry_count: u64,
+    args: InitializeSale,
+    metadata: UpdateMetadata,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let mut accounts = initialize_accounts(maker, mint, registry_count).to_vec();
+    accounts.extend(
+        InitializeSaleAccounts {
+            allocation_mint: AccountMeta::new_readonly(*allocation_mint, false),
+            allocation_vault: AccountMeta::new(
+                associated_token_address(&fundraiser, allocation_mint),
+                false,
+            ),
+            maker_allocation_ata: AccountMeta::new(
+                associated_token_address(maker, allocation_mint),
+                false,
+            ),
+        }
+        .to_vec(),
+    );
+
+    instruction(
+        FundraisingInstructions::InitializeSale,
+        accounts,
+        &[args.to_bytes(), metadata.to_bytes()].concat(),
+    )
+}
+
+fn initialize_accounts(
+    maker: &Pubkey,
+    mint: &Pubkey,
+    registry_count: u64,
+) -> InitializeAccounts<AccountMeta> {
+    let fundraiser = fundraiser_pda(maker);
+
+    InitializeAccounts {
+        maker: AccountMeta::new(*maker, true),
+        mint: AccountMeta::new_readonly(*mint, false),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        vault: AccountMeta::new_readonly(associated_token_address(&fundraiser, mint), false),
+        system_program: AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        associated_token_program: AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        rent_sysvar: AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        metadata: AccountMeta::new(metadata_pda(&fundraiser), false),
+        registry: AccountMeta::new(registry_pda(), false),
+        registry_page: AccountMeta::new(
+            registry_page_pda(registry_position(registry_count).0),
+            false,
+        ),
+    }
+}
+
+/// `args` for a fundraiser without an allowlist, see [`with_allowlist_proof`]
+pub fn contribute(
+    contributor: &Pubkey,
+    maker: &Pubkey,
+    mint: &Pubkey,
+    args: ContributeArgs,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = ContributeAccounts {
+        contributor: AccountMeta::new(*contributor, true),
+        mint: AccountMeta::new_readonly(*mint, false),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        vault: AccountMeta::new(associated_token_address(&fundraiser, mint), false),
+        contributor_ata: AccountMeta::new(associated_token_address(contributor, mint), false),
+        contributor_pda: AccountMeta::new(contributor_pda(&fundraiser, contributor), false),
+        system_program: AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        associated_token_program: AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        rent_sysvar: AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::Contribute,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+/// Appends the contributor's allowlist `cap` and its merkle proof to a [`contribute`].
+pub fn with_allowlist_proof(
+    mut instruction: Instruction,
+    args: AllowlistArgs,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    instruction.data.extend(args.to_bytes());
+    instruction.data.extend(proof.concat());
+    instruction
+}
+
+/// Appends the instructions sysvar to a [`contribute`] to a fundraiser with
+/// an attestor, which reads the voucher off the ed25519 instruction before it.
+pub fn with_voucher(mut instruction: Instruction) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ID, false));
+    instruction
+}
+
+/// Appends the event authority to an instruction, so its events are sent as
+/// `EmitEvent` inner instructions rather than logged. Goes after any other
+/// trailing accounts.
+pub fn with_event_authority(mut instruction: Instruction) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(event_authority_pda(), false));
+    instruction
+}
+
+/// `Refund`, followed by the campaign's dispute so one its arbiter let lapse
+/// can be closed out on the way
+pub fn refund(contributor: &Pubkey, maker: &Pubkey, mint: &Pubkey) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = RefundAccounts {
+        contributor: AccountMeta::new(*contributor, true),
+        maker: AccountMeta::new_readonly(*maker, false),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        vault: AccountMeta::new(associated_token_address(&fundraiser, mint), false),
+        contributor_ata: AccountMeta::new(associated_token_address(contributor, mint), false),
+        contributor_pda: AccountMeta::new(contributor_pda(&fundraiser, contributor), false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    };
+    let mut accounts = accounts.to_vec();
+    accounts.push(AccountMeta::new(dispute_pda(&fundraiser), false));
+
+    instruction(
+        FundraisingInstructions::Refund,
+        accounts,
+        &RefundArgs {}.to_bytes(),
+    )
+}
+
+/// `Claim`, paid to the authority's associated token account
+pub fn claim(authority: &Pubkey, maker: &Pubkey, mint: &Pubkey) -> Instruction {
+    claim_instruction(FundraisingInstructions::Claim, authority, maker, mint)
+}
+
+/// `ExecuteClaim` for a timelocked fundraiser, accounts as in [`claim`]
+pub fn execute_claim(authority: &Pubkey, maker: &Pubkey, mint: &Pubkey) -> Instruction {
+    claim_instruction(
+        FundraisingInstructions::ExecuteClaim,
+        authority,
+        maker,
+        mint,
+    )
+}
+
+fn claim_instruction(
+    discriminator: FundraisingInstructions,
+    authority: &Pubkey,
+    maker: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = ClaimAccounts {
+        authority: AccountMeta::new(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        vault: AccountMeta::new(associated_token_address(&fundraiser, mint), false),
+        authority_ata: AccountMeta::new(associated_token_address(authority, mint), false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(discriminator, accounts.to_vec(), &ClaimArgs {}.to_bytes())
+}
+
+/// `ClaimAllocation`, paying a contributor's project tokens out of a sale
+/// to their associated token account
+pub fn claim_allocation(
+    contributor: &Pubkey,
+    maker: &Pubkey,
+    allocation_mint: &Pubkey,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = ClaimAllocationAccounts {
+        contributor: AccountMeta::new(*contributor, true),
+        maker: AccountMeta::new_readonly(*maker, false),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        allocation_vault: AccountMeta::new(
+            associated_token_address(&fundraiser, allocation_mint),
+            false,
+        ),
+        contributor_allocation_ata: AccountMeta::new(
+            associated_token_address(contributor, allocation_mint),
+            false,
+        ),
+        contributor_pda: AccountMeta::new(contributor_pda(&fundraiser, contributor), false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::ClaimAllocation,
+        accounts.to_vec(),
+        &ClaimAllocationArgs {}.to_bytes(),
+    )
+}
+
+/// `ReclaimAllocation`, returning a failed sale's project tokens to the
+/// authority's associated token account
+pub fn reclaim_allocation(
+    authority: &Pubkey,
+    maker: &Pubkey,
+    allocation_mint: &Pubkey,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = ReclaimAllocationAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        allocation_vault: AccountMeta::new(
+            associated_token_address(&fundraiser, allocation_mint),
+            false,
+        ),
+        authority_allocation_ata: AccountMeta::new(
+            associated_token_address(authority, allocation_mint),
+            false,
+        ),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    };
+
+    instruction(
+        FundraisingInstructions::ReclaimAllocation,
+        accounts.to_vec(),
+        &ReclaimAllocationArgs {}.to_bytes(),
+    )
+}
+
+/// `Settle` for an oversubscribed sale, refunding the contributor's excess
+/// and paying their allocation in one go
+pub fn settle(
+    contributor: &Pubkey,
+    maker: &Pubkey,
+    mint: &Pubkey,
+    allocation_mint: &Pubkey,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = SettleAccounts {
+        contributor: AccountMeta::new(*contributor, true),
+        maker: AccountMeta::new_readonly(*maker, false),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        vault: AccountMeta::new(associated_token_address(&fundraiser, mint), false),
+        contributor_ata: AccountMeta::new(associated_token_address(contributor, mint), false),
+        allocation_vault: AccountMeta::new(
+            associated_token_address(&fundraiser, allocation_mint),
+            false,
+        ),
+        contributor_allocation_ata: AccountMeta::new(
+            associated_token_address(contributor, allocation_mint),
+            false,
+        ),
+        contributor_pda: AccountMeta::new(contributor_pda(&fundraiser, contributor), false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::Settle,
+        accounts.to_vec(),
+        &SettleArgs {}.to_bytes(),
+    )
+}
+
+pub fn cancel(authority: &Pubkey, maker: &Pubkey) -> Instruction {
+    let accounts = CancelAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::Cancel,
+        accounts.to_vec(),
+        &CancelArgs {}.to_bytes(),
+    )
+}
+
+/// `RequestClaim`, starting the timelock [`execute_claim`] waits out
+pub fn request_claim(authority: &Pubkey, maker: &Pubkey) -> Instruction {
+    let accounts = RequestClaimAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::RequestClaim,
+        accounts.to_vec(),
+        &RequestClaimArgs {}.to_bytes(),
+    )
+}
+
+pub fn extend(authority: &Pubkey, maker: &Pubkey, args: Extend) -> Instruction {
+    let accounts = ExtendAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::Extend,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+pub fn propose_authority(
+    authority: &Pubkey,
+    maker: &Pubkey,
+    args: ProposeAuthority,
+) -> Instruction {
+    let accounts = ProposeAuthorityAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::ProposeAuthority,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+pub fn accept_authority(new_authority: &Pubkey, maker: &Pubkey) -> Instruction {
+    let accounts = AcceptAuthorityAccounts {
+        new_authority: AccountMeta::new_readonly(*new_authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::AcceptAuthority,
+        accounts.to_vec(),
+        &AcceptAuthorityArgs {}.to_bytes(),
+    )
+}
+
+pub fn set_allowlist(authority: &Pubkey, maker: &Pubkey, args: SetAllowlist) -> Instruction {
+    let accounts = SetAllowlistAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::SetAllowlist,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+pub fn set_attestor(authority: &Pubkey, maker: &Pubkey, args: SetAttestor) -> Instruction {
+    let accounts = SetAttestorAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::SetAttestor,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+/// `SetCosigners`, which the current cosigners sign off with [`with_cosigners`]
+pub fn set_cosigners(authority: &Pubkey, maker: &Pubkey, args: SetCosigners) -> Instruction {
+    let accounts = SetCosignersAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new(fundraiser_pda(maker), false),
+    };
+
+    instruction(
+        FundraisingInstructions::SetCosigners,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+/// `ObjectClaim`, followed by the objector's contributor pda, which the
+/// program ignores when the arbiter objects
+pub fn object_claim(objector: &Pubkey, maker: &Pubkey) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let mut accounts = ObjectClaimAccounts {
+        objector: AccountMeta::new_readonly(*objector, true),
+        fundraiser: AccountMeta::new(fundraiser, false),
+    }
+    .to_vec();
+    accounts.push(AccountMeta::new(
+        contributor_pda(&fundraiser, objector),
+        false,
+    ));
+
+    instruction(
+        FundraisingInstructions::ObjectClaim,
+        accounts,
+        &ObjectClaimArgs {}.to_bytes(),
+    )
+}
+
+/// `OpenDispute`, followed by the opener's contributor pda, which the
+/// program ignores when the maker or the authority opens it
+pub fn open_dispute(opener: &Pubkey, maker: &Pubkey, args: OpenDispute) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let mut accounts = OpenDisputeAccounts {
+        opener: AccountMeta::new(*opener, true),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        dispute: AccountMeta::new(dispute_pda(&fundraiser), false),
+        system_program: AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    }
+    .to_vec();
+    accounts.push(AccountMeta::new_readonly(
+        contributor_pda(&fundraiser, opener),
+        false,
+    ));
+
+    instruction(
+        FundraisingInstructions::OpenDispute,
+        accounts,
+        &args.to_bytes(),
+    )
+}
+
+/// `ResolveDispute`, paying any share of the raise to `authority`'s
+/// associated token account
+pub fn resolve_dispute(
+    arbiter: &Pubkey,
+    maker: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    args: ResolveDispute,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = ResolveDisputeAccounts {
+        arbiter: AccountMeta::new_readonly(*arbiter, true),
+        fundraiser: AccountMeta::new(fundraiser, false),
+        dispute: AccountMeta::new(dispute_pda(&fundraiser), false),
+        vault: AccountMeta::new(associated_token_address(&fundraiser, mint), false),
+        authority_ata: AccountMeta::new(associated_token_address(authority, mint), false),
+        token_program: AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        config: AccountMeta::new_readonly(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::ResolveDispute,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+pub fn update_metadata(authority: &Pubkey, maker: &Pubkey, args: UpdateMetadata) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    let accounts = UpdateMetadataAccounts {
+        authority: AccountMeta::new_readonly(*authority, true),
+        fundraiser: AccountMeta::new_readonly(fundraiser, false),
+        metadata: AccountMeta::new(metadata_pda(&fundraiser), false),
+    };
+
+    instruction(
+        FundraisingInstructions::UpdateMetadata,
+        accounts.to_vec(),
+        &args.to_bytes(),
+    )
+}
+
+/// `Delist`, `registry_index` being the fundraiser's `registry_index`
+pub fn delist(maker: &Pubkey, registry_index: u64) -> Instruction {
+    let accounts = DelistAccounts {
+        fundraiser: AccountMeta::new_readonly(fundraiser_pda(maker), false),
+        registry_page: AccountMeta::new(
+            registry_page_pda(registry_position(registry_index).0),
+            false,
+        ),
+    };
+
+    instruction(
+        FundraisingInstructions::Delist,
+        accounts.to_vec(),
+        &DelistArgs {}.to_bytes(),
+    )
+}
+
+/// `Migrate` for a version 1 fundraiser, its authority vouching for the vault
+pub fn migrate_fundraiser(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    maker: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let fundraiser = fundraiser_pda(maker);
+
+    migrate_instruction(
+        payer,
+        &fundraiser,
+        [
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(associated_token_address(&fundraiser, mint), false),
+        ],
+    )
+}
+
+/// `Migrate` for a version 1 contributor ledger
+pub fn migrate_contributor(payer: &Pubkey, fundraiser: &Pubkey, wallet: &Pubkey) -> Instruction {
+    migrate_instruction(
+        payer,
+        &contributor_pda(fundraiser, wallet),
+        [
+            AccountMeta::new_readonly(*fundraiser, false),
+            AccountMeta::new_readonly(*wallet, false),
+        ],
+    )
+}
+
+fn migrate_instruction(payer: &Pubkey, account: &Pubkey, kind: [AccountMeta; 2]) -> Instruction {
+    let mut accounts = MigrateAccounts {
+        payer: AccountMeta::new(*payer, true),
+        account: AccountMeta::new(*account, false),
+        system_program: AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    }
+    .to_vec();
+    accounts.extend(kind);
+
+    instruction(
+        FundraisingInstructions::Migrate,
+        accounts,
+        &MigrateArgs {}.to_bytes(),
+    )
+}
+
+/// Creates the config with `guardian`, who must be the program's upgrade authority.
+pub fn initialize_config(guardian: &Pubkey) -> Instruction {
+    let accounts = InitializeConfigAccounts {
+        guardian: AccountMeta::new(*guardian, true),
+        config: AccountMeta::new(config_pda(), false),
+        program_data: AccountMeta::new_readonly(program_data_address(), false),
+        system_program: AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    };
+
+    instruction(
+        FundraisingInstructions::InitializeConfig,
+        accounts.to_vec(),
+        &InitializeConfigArgs {}.to_bytes(),
+    )
+}
+
+/// Hands the guardian role from `guardian` to `new_guardian`, both signing.
+pub fn set_guardian(guardian: &Pubkey, new_guardian: &Pubkey) -> Instruction {
+    let accounts = SetGuardianAccounts {
+        guardian: AccountMeta::new_readonly(*guardian, true),
+        new_guardian: AccountMeta::new_readonly(*new_guardian, true),
+        config: AccountMeta::new(config_pda(), false),
+    };
+
+    instruction(
+        FundraisingInstructions::SetGuardian,
+        accounts.to_vec(),
+        &SetGuardianArgs {}.to_bytes(),
+    )
+}
+
+/// Pauses or unpauses `fundraiser` as the guardian, the whole program when `None`.
+pub fn set_pause(guardian: &Pubkey, fundraiser: Option<&Pubkey>, args: SetPause) -> Instruction {
+    let mut accounts = SetPauseAccounts {
+        guardian: AccountMeta::new_readonly(*guardian, true),
+        config: AccountMeta::new(config_pda(), false),
+    }
+    .to_vec();
+    accounts.extend(fundraiser.map(|fundraiser| AccountMeta::new(*fundraiser, false)));
+
+    instruction(
+        FundraisingInstructions::SetPause,
+        accounts,
+        &args.to_bytes(),
+    )
+}
+
+/// Appends signing cosigners to an admin instruction such as [`claim`] or [`cancel`].
+pub fn with_cosigners(mut instruction: Instruction, cosigners: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        cosigners
+            .iter()
+            .map(|cosigner| AccountMeta::new_readonly(*cosigner, true)),
+    );
+    instruction
+}
+
+/// System program instructions creating a durable nonce account at `nonce`
+/// controlled by `authority`, `lamports` covering its rent exemption.
+pub fn create_nonce_account(
+    payer: &Pubkey,
+    nonce: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    solana_system_interface::instruction::create_nonce_account(payer, nonce, authority, lamports)
+}
+
+/// The system program's `AdvanceNonceAccount`, which must come first in a
+/// transaction using the nonce in place of a recent blockhash.
+pub fn advance_nonce_account(nonce: &Pubkey, authority: &Pubkey) -> Instruction {
+    solana_system_interface::instruction::advance_nonce_account(nonce, authority)
+}
+
+/// Prepends [`advance_nonce_account`] to `instructions`, such as a [`claim`]
+/// waiting on slow cosigners, so the transaction stays valid until the nonce
+/// is used. Sign it with the value [`decode_nonce`] reads as its blockhash.
+pub fn with_durable_nonce(
+    instructions: &[Instruction],
+    nonce: &Pubkey,
+    authority: &Pubkey,
+) -> Vec<Instruction> {
+    [&[advance_nonce_account(nonce, authority)][..], instructions].concat()
+}
+
+/// The authority and stored value of an initialized nonce account.
+pub fn decode_nonce(data: &[u8]) -> Option<(Pubkey, [u8; 32])> {
+    // `Versions::Current(State::Initialized(Data { authority, durable_nonce, .. }))`
+    if data.len() != NONCE_ACCOUNT_LEN || data[..8] != [1, 0, 0, 0, 1, 0, 0, 0] {
+        return None;
+    }
+
+    let authority = Pubkey::new_from_array(data[8..40].try_into().ok()?);
+    Some((authority, data[40..72].try_into().ok()?))
+}
@@ -0,0 +1,342 @@
+//! Typed events for indexers.
+//!
+//! Every event is a one byte discriminator followed by the event struct in its
+//! fixed little endian layout. By default it is logged with `sol_log_data` as
+//! those two slices, which the runtime prints as `Program data: <base64> <base64>`
+//! and [`FundraiserEvent::from_logs`] turns back into events.
+//!
+//! Logs get truncated on busy transactions. When the event authority PDA is
+//! among an instruction's accounts, the event is instead sent to this program
+//! in an `EmitEvent` CPI signed by that PDA, so it shows up in the transaction's
+//! inner instructions, see [`FundraiserEvent::from_instruction_data`].
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    instructions::{AccountSpec, FundraisingInstructions},
+    state::MAX_COSIGNERS,
+};
+
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// `find_program_address(&[EVENT_AUTHORITY_SEED], &ID)`, worked out ahead of
+/// time so emitting an event doesn't pay for the bump search
+pub const EVENT_AUTHORITY: Pubkey =
+    pinocchio_pubkey::pubkey!("2uR8LbzSmcfE3LSVTAyfgmG3VUWhdHVfCYCtFFsDPRc7");
+pub const EVENT_AUTHORITY_BUMP: u8 = 255;
+
+/// what `Event::emit_to` looks for after an instruction's accounts
+pub const EVENT_AUTHORITY_ACCOUNT: AccountSpec = AccountSpec::new("event_authority").optional();
+
+pub fn event_authority() -> (Pubkey, u8) {
+    (EVENT_AUTHORITY, EVENT_AUTHORITY_BUMP)
+}
+
+pub trait Event: Pod {
+    const DISCRIMINATOR: u8;
+
+    /// logs the event
+    fn emit(&self) {
+        pinocchio::log::sol_log_data(&[&[Self::DISCRIMINATOR], bytemuck::bytes_of(self)]);
+    }
+
+    /// self-CPIs the event if the event authority is in `accounts`, logs it otherwise
+    fn emit_to(&self, accounts: &[AccountInfo]) -> ProgramResult {
+        let Some(event_authority) = accounts
+            .iter()
+            .find(|account| account.key() == &EVENT_AUTHORITY)
+        else {
+            self.emit();
+            return Ok(());
+        };
+
+        let data = [
+            &[
+                FundraisingInstructions::EmitEvent as u8,
+                Self::DISCRIMINATOR,
+            ][..],
+            bytemuck::bytes_of(self),
+        ]
+        .concat();
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &[AccountMeta::new(event_authority.key(), false, true)],
+            data: &data,
+        };
+
+        let bump = [EVENT_AUTHORITY_BUMP];
+        let seed = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump)];
+        pinocchio::cpi::invoke_signed(&instruction, &[event_authority], &[Signer::from(&seed)])
+    }
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct FundraiserCreated {
+    pub fundraiser: [u8; 32],
+    pub maker: [u8; 32],
+    pub mint_to_raise: [u8; 32],
+    pub amount_to_raise: u64,
+    pub time_started: i64,
+    pub duration: u64,
+}
+
+impl Event for FundraiserCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Contributed {
+    pub fundraiser: [u8; 32],
+    pub contributor: [u8; 32],
+    pub amount: u64,
+    /// fundraiser total after this contribution
+    pub current_amount: u64,
+}
+
+impl Event for Contributed {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Refunded {
+    pub fundraiser: [u8; 32],
+    pub contributor: [u8; 32],
+    /// what was paid back, less any split the arbiter awarded the authority
+    pub amount: u64,
+    /// fundraiser total after this refund
+    pub current_amount: u64,
+}
+
+impl Event for Refunded {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Claimed {
+    pub fundraiser: [u8; 32],
+    /// who received the raise
+    pub authority: [u8; 32],
+    pub amount: u64,
+}
+
+impl Event for Claimed {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Cancelled {
+    pub fundraiser: [u8; 32],
+    /// who cancelled, all zeroes when a lapsed dispute did
+    pub authority: [u8; 32],
+}
+
+impl Event for Cancelled {
+    const DISCRIMINATOR: u8 = 4;
+}
+
+/// The guardian paused a fundraiser, or the whole program when `fundraiser`
+/// is all zeroes.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Paused {
+    pub fundraiser: [u8; 32],
+    pub guardian: [u8; 32],
+    pub reason: u8,
+    pub paused_at: [u8; 8],
+}
+
+impl Event for Paused {
+    const DISCRIMINATOR: u8 = 5;
+}
+
+/// The guardian lifted a pause, laid out as [`Paused`].
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Unpaused {
+    pub fundraiser: [u8; 32],
+    pub guardian: [u8; 32],
+    pub reason: u8,
+    pub unpaused_at: [u8; 8],
+}
+
+impl Event for Unpaused {
+    const DISCRIMINATOR: u8 = 6;
+}
+
+/// all zeroes as `new_authority` withdraws a pending proposal
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct AuthorityProposed {
+    pub fundraiser: [u8; 32],
+    pub authority: [u8; 32],
+    pub new_authority: [u8; 32],
+}
+
+impl Event for AuthorityProposed {
+    const DISCRIMINATOR: u8 = 7;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct AuthorityAccepted {
+    pub fundraiser: [u8; 32],
+    pub previous_authority: [u8; 32],
+    pub new_authority: [u8; 32],
+}
+
+impl Event for AuthorityAccepted {
+    const DISCRIMINATOR: u8 = 8;
+}
+
+/// the new cosigner set, only the first `cosigner_count` are used
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct CosignersSet {
+    pub fundraiser: [u8; 32],
+    pub authority: [u8; 32],
+    pub threshold: u8,
+    pub cosigner_count: u8,
+    pub cosigners: [[u8; 32]; MAX_COSIGNERS],
+}
+
+impl Event for CosignersSet {
+    const DISCRIMINATOR: u8 = 9;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct DisputeOpened {
+    pub fundraiser: [u8; 32],
+    pub opened_by: [u8; 32],
+    pub reason: u8,
+}
+
+impl Event for DisputeOpened {
+    const DISCRIMINATOR: u8 = 10;
+}
+
+/// The arbiter's ruling, followed by the [`Cancelled`] and [`Claimed`] it
+/// amounts to. A dispute the arbiter let lapse resolves as a `Refund` with
+/// `arbiter` all zeroes.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct DisputeResolved {
+    pub fundraiser: [u8; 32],
+    pub arbiter: [u8; 32],
+    /// a `Resolution`
+    pub resolution: u8,
+    pub maker_share_bps: [u8; 2],
+}
+
+impl Event for DisputeResolved {
+    const DISCRIMINATOR: u8 = 11;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ClaimObjected {
+    pub fundraiser: [u8; 32],
+    pub objector: [u8; 32],
+    /// contributions objecting so far, unchanged by the arbiter
+    pub objections: [u8; 8],
+    /// whether the claim is now blocked
+    pub claim_blocked: u8,
+}
+
+impl Event for ClaimObjected {
+    const DISCRIMINATOR: u8 = 12;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FundraiserEvent {
+    FundraiserCreated(FundraiserCreated),
+    Contributed(Contributed),
+    Refunded(Refunded),
+    Claimed(Claimed),
+    Cancelled(Cancelled),
+    Paused(Paused),
+    Unpaused(Unpaused),
+    AuthorityProposed(AuthorityProposed),
+    AuthorityAccepted(AuthorityAccepted),
+    CosignersSet(CosignersSet),
+    DisputeOpened(DisputeOpened),
+    DisputeResolved(DisputeResolved),
+    ClaimObjected(ClaimObjected),
+}
+
+impl FundraiserEvent {
+    /// Decodes the slices of one `sol_log_data` call, `None` if they are not an event.
+    pub fn decode(discriminator: &[u8], data: &[u8]) -> Option<Self> {
+        fn read<E: Event>(data: &[u8]) -> Option<E> {
+            bytemuck::try_pod_read_unaligned::<E>(data).ok()
+        }
+
+        match discriminator {
+            [FundraiserCreated::DISCRIMINATOR] => read(data).map(Self::FundraiserCreated),
+            [Contributed::DISCRIMINATOR] => read(data).map(Self::Contributed),
+            [Refunded::DISCRIMINATOR] => read(data).map(Self::Refunded),
+            [Claimed::DISCRIMINATOR] => read(data).map(Self::Claimed),
+            [Cancelled::DISCRIMINATOR] => read(data).map(Self::Cancelled),
+            [Paused::DISCRIMINATOR] => read(data).map(Self::Paused),
+            [Unpaused::DISCRIMINATOR] => read(data).map(Self::Unpaused),
+            [AuthorityProposed::DISCRIMINATOR] => read(data).map(Self::AuthorityProposed),
+            [AuthorityAccepted::DISCRIMINATOR] => read(data).map(Self::AuthorityAccepted),
+            [CosignersSet::DISCRIMINATOR] => read(data).map(Self::CosignersSet),
+            [DisputeOpened::DISCRIMINATOR] => read(data).map(Self::DisputeOpened),
+            [DisputeResolved::DISCRIMINATOR] => read(data).map(Self::DisputeResolved),
+            [ClaimObjected::DISCRIMINATOR] => read(data).map(Self::ClaimObjected),
+            _ => None,
+        }
+    }
+
+    /// Decodes an `EmitEvent` inner instruction, `None` if it is not one.
+    /// `program_id` is the program it invoked, since another program's data
+    /// may happen to decode too.
+    pub fn from_instruction_data(program_id: &Pubkey, data: &[u8]) -> Option<Self> {
+        if program_id != &crate::ID {
+            return None;
+        }
+
+        match data {
+            [instruction, discriminator, event @ ..]
+                if *instruction == FundraisingInstructions::EmitEvent as u8 =>
+            {
+                Self::decode(&[*discriminator], event)
+            }
+            _ => None,
+        }
+    }
+
+    /// Every event in a transaction's log messages, in the order they were emitted.
+    ///
+    /// Only pass logs of transactions that invoked this program, as other
+    /// programs' `Program data:` lines may happen to decode too.
+    #[cfg(not(target_os = "solana"))]
+    pub fn from_logs<S: AsRef<str>>(logs: &[S]) -> Vec<Self> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        logs.iter()
+            .filter_map(|log| log.as_ref().strip_prefix("Program data: "))
+            .filter_map(|data| {
+                let mut slices = data.split(' ').map(|slice| STANDARD.decode(slice).ok());
+                match (slices.next(), slices.next(), slices.next()) {
+                    (Some(Some(discriminator)), Some(Some(data)), None) => {
+                        Self::decode(&discriminator, &data)
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,738 @@
+//! Anchor/Codama compatible IDL, behind the `idl` feature and in tests.
+//!
+//! Everything here is read off the program's own definitions: account lists
+//! come from `instruction_accounts!`, and argument and state layouts from the
+//! Pod structs themselves. `describe!` only names the fields, and building
+//! the IDL panics if they no longer add up to the struct, so a changed layout
+//! cannot go out with a stale IDL. `cargo run --features idl --bin idl`
+//! prints it.
+
+use pinocchio::program_error::ProgramError;
+use serde_json::{json, Value};
+
+use crate::{
+    client::PROGRAM_ID,
+    events::{
+        AuthorityAccepted, AuthorityProposed, Cancelled, ClaimObjected, Claimed, Contributed,
+        CosignersSet, DisputeOpened, DisputeResolved, Event, FundraiserCreated, Paused, Refunded,
+        Unpaused, EVENT_AUTHORITY_ACCOUNT,
+    },
+    instructions::*,
+    state::{
+        CampaignMetadata, Config, Contributor, Dispute, Fundraiser, ProgramAccount, Registry,
+        RegistryEntry, RegistryPage,
+    },
+};
+
+/// A Rust type the IDL can name.
+pub trait IdlType {
+    fn idl_type() -> Value;
+}
+
+impl IdlType for u8 {
+    fn idl_type() -> Value {
+        json!("u8")
+    }
+}
+
+impl IdlType for u64 {
+    fn idl_type() -> Value {
+        json!("u64")
+    }
+}
+
+impl IdlType for i64 {
+    fn idl_type() -> Value {
+        json!("i64")
+    }
+}
+
+impl<T: IdlType, const N: usize> IdlType for [T; N] {
+    fn idl_type() -> Value {
+        json!({ "array": [T::idl_type(), N] })
+    }
+}
+
+pub struct IdlField {
+    pub name: &'static str,
+    pub ty: Value,
+    pub size: usize,
+    pub docs: &'static [&'static str],
+}
+
+/// A Pod struct listed under the IDL's `types`, see `describe!`.
+pub trait IdlStruct {
+    const NAME: &'static str;
+    const SIZE: usize;
+
+    fn fields() -> Vec<IdlField>;
+}
+
+/// Names `S`'s field, typed as `T` unless the bytes hold a `primitive`, such
+/// as the little endian `[u8; 8]` amounts of the state structs.
+fn field<S, T: IdlType>(
+    name: &'static str,
+    _: fn(&S) -> &T,
+    primitive: Option<&'static str>,
+) -> IdlField {
+    let size = core::mem::size_of::<T>();
+    let ty = match primitive {
+        Some(primitive) => {
+            let primitive_size = match primitive {
+                "u8" | "bool" => 1,
+                "u16" => 2,
+                "u64" | "i64" => 8,
+                "pubkey" => 32,
+                _ => panic!("unknown primitive {primitive}"),
+            };
+            assert_eq!(primitive_size, size, "{name} does not hold a {primitive}");
+            json!(primitive)
+        }
+        None => T::idl_type(),
+    };
+
+    IdlField {
+        name,
+        ty,
+        size,
+        docs: &[],
+    }
+}
+
+/// Lists a struct's fields in order, optionally `as` the primitive they hold.
+macro_rules! describe {
+    ($($name:ident { $($field:ident $(as $primitive:ident)?),* $(,)? })*) => {$(
+        impl IdlStruct for $name {
+            const NAME: &'static str = stringify!($name);
+            const SIZE: usize = core::mem::size_of::<$name>();
+
+            fn fields() -> Vec<IdlField> {
+                vec![$(
+                    field(
+                        stringify!($field),
+                        |s: &$name| &s.$field,
+                        None $(.or(Some(stringify!($primitive))))?,
+                    ),
+                )*]
+            }
+        }
+
+        impl IdlType for $name {
+            fn idl_type() -> Value {
+                json!({ "defined": { "name": stringify!($name) } })
+            }
+        }
+    )*};
+}
+
+describe! {
+    InitializeFundraiser {
+        amount_to_raise,
+        duration,
+        claim_timelock,
+        arbiter as pubkey,
+    }
+    InitializeSale {
+        fundraiser,
+        allocation_numerator,
+        allocation_denominator,
+        allow_oversubscription,
+    }
+    ContributeArgs { amount }
+    AllowlistArgs { cap }
+    SetAllowlist { root }
+    SetAttestor { attestor as pubkey }
+    SetPause { paused as bool, reason }
+    ProposeAuthority { new_authority as pubkey }
+    Extend { duration }
+    SetCosigners { threshold, cosigner_count, cosigners }
+    OpenDispute { reason }
+    ResolveDispute { resolution, maker_share_bps as u16 }
+    RefundArgs {}
+    ClaimArgs {}
+    ClaimAllocationArgs {}
+    ReclaimAllocationArgs {}
+    SettleArgs {}
+    InitializeConfigArgs {}
+    SetGuardianArgs {}
+    AcceptAuthorityArgs {}
+    CancelArgs {}
+    RequestClaimArgs {}
+    ObjectClaimArgs {}
+    DelistArgs {}
+    MigrateArgs {}
+    UpdateMetadata {
+        title_len,
+        title,
+        uri_len,
+        uri,
+        category,
+        image_hash,
+    }
+
+    Fundraiser {
+        discriminator,
+        version as u8,
+        maker as pubkey,
+        mint_to_raise as pubkey,
+        amount_to_raise as u64,
+        current_amount as u64,
+        time_started as i64,
+        duration as u64,
+        bump as u8,
+        allocation_mint as pubkey,
+        allocation_numerator as u64,
+        allocation_denominator as u64,
+        allow_oversubscription as bool,
+        claimed as bool,
+        allowlist_root,
+        attestor as pubkey,
+        paused as bool,
+        pause_reason as u8,
+        paused_at as i64,
+        authority as pubkey,
+        pending_authority as pubkey,
+        cancelled as bool,
+        threshold as u8,
+        cosigner_count as u8,
+        cosigners,
+        claim_timelock as u64,
+        claim_requested_at as i64,
+        arbiter as pubkey,
+        objections as u64,
+        claim_blocked as bool,
+        disputed as bool,
+        maker_share_bps as u16,
+        registry_index as u64,
+        vault as pubkey,
+    }
+    Contributor {
+        discriminator,
+        version as u8,
+        amount as u64,
+        attestation_nonce as u64,
+        objected as bool,
+        fundraiser as pubkey,
+        wallet as pubkey,
+    }
+    Config {
+        discriminator,
+        version as u8,
+        guardian as pubkey,
+        paused as bool,
+        pause_reason as u8,
+        paused_at as i64,
+        bump as u8,
+    }
+    Dispute {
+        discriminator,
+        version as u8,
+        fundraiser as pubkey,
+        opened_by as pubkey,
+        reason as u8,
+        opened_at as i64,
+        resolved as bool,
+        resolution as u8,
+        maker_share_bps as u16,
+        resolved_at as i64,
+        bump as u8,
+    }
+    CampaignMetadata {
+        discriminator,
+        version as u8,
+        fundraiser as pubkey,
+        title_len as u8,
+        title,
+        uri_len as u8,
+        uri,
+        category as u8,
+        image_hash,
+        bump as u8,
+    }
+    Registry {
+        discriminator,
+        version as u8,
+        count as u64,
+        bump as u8,
+    }
+    RegistryEntry {
+        fundraiser as pubkey,
+        category as u8,
+        closed as bool,
+    }
+    RegistryPage {
+        discriminator,
+        version as u8,
+        index as u64,
+        len as u8,
+        bump as u8,
+        entries,
+    }
+
+    FundraiserCreated {
+        fundraiser as pubkey,
+        maker as pubkey,
+        mint_to_raise as pubkey,
+        amount_to_raise,
+        time_started,
+        duration,
+    }
+    Contributed {
+        fundraiser as pubkey,
+        contributor as pubkey,
+        amount,
+        current_amount,
+    }
+    Refunded {
+        fundraiser as pubkey,
+        contributor as pubkey,
+        amount,
+        current_amount,
+    }
+    Claimed {
+        fundraiser as pubkey,
+        authority as pubkey,
+        amount,
+    }
+    Cancelled {
+        fundraiser as pubkey,
+        authority as pubkey,
+    }
+    Paused {
+        fundraiser as pubkey,
+        guardian as pubkey,
+        reason,
+        paused_at as i64,
+    }
+    Unpaused {
+        fundraiser as pubkey,
+        guardian as pubkey,
+        reason,
+        unpaused_at as i64,
+    }
+    AuthorityProposed {
+        fundraiser as pubkey,
+        authority as pubkey,
+        new_authority as pubkey,
+    }
+    AuthorityAccepted {
+        fundraiser as pubkey,
+        previous_authority as pubkey,
+        new_authority as pubkey,
+    }
+    CosignersSet {
+        fundraiser as pubkey,
+        authority as pubkey,
+        threshold,
+        cosigner_count,
+        cosigners,
+    }
+    DisputeOpened {
+        fundraiser as pubkey,
+        opened_by as pubkey,
+        reason,
+    }
+    DisputeResolved {
+        fundraiser as pubkey,
+        arbiter as pubkey,
+        resolution,
+        maker_share_bps as u16,
+    }
+    ClaimObjected {
+        fundraiser as pubkey,
+        objector as pubkey,
+        objections as u64,
+        claim_blocked as bool,
+    }
+}
+
+/// the struct's fields after its first `skip` bytes, checked to cover the rest of it
+fn struct_fields<S: IdlStruct>(skip: usize) -> Vec<IdlField> {
+    let mut fields = S::fields();
+    let mut skipped = 0;
+    while skipped < skip {
+        skipped += fields.remove(0).size;
+    }
+    assert_eq!(
+        skipped,
+        skip,
+        "{} does not start with {skip} bytes",
+        S::NAME
+    );
+    assert_eq!(
+        skip + fields.iter().map(|field| field.size).sum::<usize>(),
+        S::SIZE,
+        "describe! is missing fields of {}",
+        S::NAME
+    );
+
+    fields
+}
+
+fn fields_json(fields: Vec<IdlField>) -> Vec<Value> {
+    fields
+        .into_iter()
+        .map(|field| match field.docs {
+            [] => json!({ "name": field.name, "type": field.ty }),
+            docs => json!({ "name": field.name, "docs": docs, "type": field.ty }),
+        })
+        .collect()
+}
+
+fn type_json<S: IdlStruct>(skip: usize) -> Value {
+    json!({
+        "name": S::NAME,
+        "type": { "kind": "struct", "fields": fields_json(struct_fields::<S>(skip)) },
+    })
+}
+
+/// accounts keep their one byte discriminator out of their type, as Anchor does
+fn account<S: IdlStruct + ProgramAccount>() -> (Value, Value) {
+    (
+        json!({ "name": S::NAME, "discriminator": [S::DISCRIMINATOR as u8] }),
+        type_json::<S>(1),
+    )
+}
+
+fn event<S: IdlStruct + Event>() -> (Value, Value) {
+    (
+        json!({ "name": S::NAME, "discriminator": [S::DISCRIMINATOR] }),
+        type_json::<S>(0),
+    )
+}
+
+fn instruction(
+    name: &str,
+    discriminator: FundraisingInstructions,
+    accounts: Vec<AccountSpec>,
+    args: Vec<IdlField>,
+) -> Value {
+    json!({
+        "name": name,
+        "discriminator": [discriminator as u8],
+        "accounts": accounts
+            .iter()
+            .map(|account| json!({
+                "name": account.name,
+                "writable": account.writable,
+                "signer": account.signer,
+                "optional": account.optional,
+            }))
+            .collect::<Vec<_>>(),
+        "args": fields_json(args),
+    })
+}
+
+/// the arguments of an instruction, made of `S` and what follows it
+fn args<S: IdlStruct>() -> Vec<IdlField> {
+    struct_fields::<S>(0)
+}
+
+/// `S`'s fields as arguments only some callers send, after the others
+fn trailing_args<S: IdlStruct>(docs: &'static [&'static str]) -> Vec<IdlField> {
+    struct_fields::<S>(0)
+        .into_iter()
+        .map(|field| IdlField { docs, ..field })
+        .collect()
+}
+
+/// a named account list followed by the accounts that may trail it
+fn accounts(named: &[AccountSpec], trailing: &[AccountSpec]) -> Vec<AccountSpec> {
+    [named, trailing].concat()
+}
+
+/// whether the processor emits events, so also takes the event authority
+fn emits_events(instruction: &FundraisingInstructions) -> bool {
+    use FundraisingInstructions as Ix;
+
+    matches!(
+        instruction,
+        Ix::Initialize
+            | Ix::Contribute
+            | Ix::Refund
+            | Ix::Claim
+            | Ix::ExecuteClaim
+            | Ix::Cancel
+            | Ix::SetPause
+            | Ix::ProposeAuthority
+            | Ix::AcceptAuthority
+            | Ix::SetCosigners
+            | Ix::ObjectClaim
+            | Ix::OpenDispute
+            | Ix::ResolveDispute
+    )
+}
+
+/// `ContributeArgs`, then for allowlisted fundraisers `AllowlistArgs` and the
+/// merkle proof, which runs to the end of the data without a length prefix
+fn contribute_args() -> Vec<IdlField> {
+    const ALLOWLIST: &[&str] = &["only sent when the fundraiser has an allowlist"];
+
+    let proof = IdlField {
+        name: "proof",
+        ty: json!({ "vec": <[u8; 32]>::idl_type() }),
+        size: 0,
+        docs: &[
+            "only sent when the fundraiser has an allowlist",
+            "the rest of the instruction data, without a length prefix",
+        ],
+    };
+
+    [
+        args::<ContributeArgs>(),
+        trailing_args::<AllowlistArgs>(ALLOWLIST),
+        vec![proof],
+    ]
+    .concat()
+}
+
+fn instructions() -> Vec<Value> {
+    use FundraisingInstructions as Ix;
+
+    (0..=u8::MAX)
+        .map_while(|discriminator| Ix::try_from(&discriminator).ok())
+        .filter_map(|discriminator| {
+            let (name, accounts, args) = match discriminator {
+                Ix::Initialize => (
+                    "initialize",
+                    InitializeAccounts::<()>::ACCOUNTS.to_vec(),
+                    [args::<InitializeFundraiser>(), args::<UpdateMetadata>()].concat(),
+                ),
+                Ix::Contribute => (
+                    "contribute",
+                    accounts(
+                        ContributeAccounts::<()>::ACCOUNTS,
+                        ContributeAccounts::<()>::TRAILING,
+                    ),
+                    contribute_args(),
+                ),
+                // reserved, the program rejects it
+                Ix::CheckContributions => return None,
+                Ix::Refund => (
+                    "refund",
+                    accounts(
+                        RefundAccounts::<()>::ACCOUNTS,
+                        RefundAccounts::<()>::TRAILING,
+                    ),
+                    args::<RefundArgs>(),
+                ),
+                Ix::Claim => (
+                    "claim",
+                    ClaimAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ClaimArgs>(),
+                ),
+                Ix::InitializeSale => (
+                    "initialize_sale",
+                    [
+                        InitializeAccounts::<()>::ACCOUNTS,
+                        InitializeSaleAccounts::<()>::ACCOUNTS,
+                    ]
+                    .concat(),
+                    [args::<InitializeSale>(), args::<UpdateMetadata>()].concat(),
+                ),
+                Ix::ClaimAllocation => (
+                    "claim_allocation",
+                    ClaimAllocationAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ClaimAllocationArgs>(),
+                ),
+                Ix::ReclaimAllocation => (
+                    "reclaim_allocation",
+                    ReclaimAllocationAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ReclaimAllocationArgs>(),
+                ),
+                Ix::Settle => (
+                    "settle",
+                    SettleAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<SettleArgs>(),
+                ),
+                Ix::SetAllowlist => (
+                    "set_allowlist",
+                    SetAllowlistAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<SetAllowlist>(),
+                ),
+                Ix::SetAttestor => (
+                    "set_attestor",
+                    SetAttestorAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<SetAttestor>(),
+                ),
+                Ix::InitializeConfig => (
+                    "initialize_config",
+                    InitializeConfigAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<InitializeConfigArgs>(),
+                ),
+                Ix::SetPause => (
+                    "set_pause",
+                    accounts(
+                        SetPauseAccounts::<()>::ACCOUNTS,
+                        SetPauseAccounts::<()>::TRAILING,
+                    ),
+                    args::<SetPause>(),
+                ),
+                Ix::ProposeAuthority => (
+                    "propose_authority",
+                    ProposeAuthorityAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ProposeAuthority>(),
+                ),
+                Ix::AcceptAuthority => (
+                    "accept_authority",
+                    AcceptAuthorityAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<AcceptAuthorityArgs>(),
+                ),
+                Ix::Cancel => (
+                    "cancel",
+                    CancelAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<CancelArgs>(),
+                ),
+                Ix::Extend => (
+                    "extend",
+                    ExtendAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<Extend>(),
+                ),
+                Ix::SetCosigners => (
+                    "set_cosigners",
+                    SetCosignersAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<SetCosigners>(),
+                ),
+                Ix::RequestClaim => (
+                    "request_claim",
+                    RequestClaimAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<RequestClaimArgs>(),
+                ),
+                Ix::ObjectClaim => (
+                    "object_claim",
+                    accounts(
+                        ObjectClaimAccounts::<()>::ACCOUNTS,
+                        ObjectClaimAccounts::<()>::TRAILING,
+                    ),
+                    args::<ObjectClaimArgs>(),
+                ),
+                Ix::ExecuteClaim => (
+                    "execute_claim",
+                    ClaimAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ClaimArgs>(),
+                ),
+                Ix::OpenDispute => (
+                    "open_dispute",
+                    accounts(
+                        OpenDisputeAccounts::<()>::ACCOUNTS,
+                        OpenDisputeAccounts::<()>::TRAILING,
+                    ),
+                    args::<OpenDispute>(),
+                ),
+                Ix::ResolveDispute => (
+                    "resolve_dispute",
+                    ResolveDisputeAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<ResolveDispute>(),
+                ),
+                Ix::UpdateMetadata => (
+                    "update_metadata",
+                    UpdateMetadataAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<UpdateMetadata>(),
+                ),
+                Ix::Delist => (
+                    "delist",
+                    DelistAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<DelistArgs>(),
+                ),
+                // its data is an event, which only the program itself sends
+                Ix::EmitEvent => (
+                    "emit_event",
+                    EmitEventAccounts::<()>::ACCOUNTS.to_vec(),
+                    vec![],
+                ),
+                Ix::Migrate => (
+                    "migrate",
+                    MigrateAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<MigrateArgs>(),
+                ),
+                Ix::SetGuardian => (
+                    "set_guardian",
+                    SetGuardianAccounts::<()>::ACCOUNTS.to_vec(),
+                    args::<SetGuardianArgs>(),
+                ),
+            };
+
+            let accounts = if emits_events(&discriminator) {
+                [accounts, vec![EVENT_AUTHORITY_ACCOUNT]].concat()
+            } else {
+                accounts
+            };
+
+            Some(instruction(name, discriminator, accounts, args))
+        })
+        .collect()
+}
+
+/// The errors processors return. Failed checks are assertions, which abort
+/// the transaction without a code, so these are the runtime's builtin errors,
+/// which the processors and the programs they call return through `?`.
+fn errors() -> Vec<Value> {
+    // builtin codes are their index shifted past the custom error range,
+    // index 1 being `Custom(0)`
+    (2u64..)
+        .map(|index| index << 32)
+        .map_while(|code| match ProgramError::from(code) {
+            ProgramError::Custom(_) => None,
+            error => Some((code, error)),
+        })
+        .map(|(code, error)| {
+            let name = format!("{error:?}");
+            assert_eq!(u64::from(error), code, "{name} does not round trip");
+            json!({ "code": code, "name": name })
+        })
+        .collect()
+}
+
+pub fn idl() -> Value {
+    let (accounts, account_types): (Vec<_>, Vec<_>) = [
+        account::<Fundraiser>(),
+        account::<Contributor>(),
+        account::<Config>(),
+        account::<Dispute>(),
+        account::<CampaignMetadata>(),
+        account::<Registry>(),
+        account::<RegistryPage>(),
+    ]
+    .into_iter()
+    .unzip();
+    let (events, event_types): (Vec<_>, Vec<_>) = [
+        event::<FundraiserCreated>(),
+        event::<Contributed>(),
+        event::<Refunded>(),
+        event::<Claimed>(),
+        event::<Cancelled>(),
+        event::<Paused>(),
+        event::<Unpaused>(),
+        event::<AuthorityProposed>(),
+        event::<AuthorityAccepted>(),
+        event::<CosignersSet>(),
+        event::<DisputeOpened>(),
+        event::<DisputeResolved>(),
+        event::<ClaimObjected>(),
+    ]
+    .into_iter()
+    .unzip();
+    let types = [
+        account_types,
+        event_types,
+        vec![
+            type_json::<InitializeFundraiser>(0),
+            type_json::<RegistryEntry>(0),
+        ],
+    ]
+    .concat();
+
+    json!({
+        "address": PROGRAM_ID.to_string(),
+        "metadata": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "spec": "0.1.0",
+        },
+        "instructions": instructions(),
+        "accounts": accounts,
+        "events": events,
+        "errors": errors(),
+        "types": types,
+    })
+}
@@ -0,0 +1,72 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    events::{AuthorityAccepted, Event},
+    state::{Fundraiser, ProgramAccount},
+};
+
+empty_args! {
+    AcceptAuthorityArgs
+}
+
+instruction_accounts! {
+    AcceptAuthorityAccounts {
+        new_authority(signer),
+        fundraiser(writable),
+    }
+}
+
+pub fn process_accept_authority(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<AcceptAuthorityArgs>(data)?;
+
+    let (
+        AcceptAuthorityAccounts {
+            new_authority,
+            fundraiser,
+        },
+        _,
+    ) = AcceptAuthorityAccounts::parse(accounts)?;
+
+    // check that the new authority is a signer ✅
+    assert!(
+        &new_authority.is_signer(),
+        "New authority should be a signer"
+    );
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let previous_authority = {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        // check that this key was proposed ✅
+        assert!(
+            fundraiser_state.pending_authority != [0u8; 32],
+            "No authority was proposed"
+        );
+        assert_eq!(
+            new_authority.key(),
+            &fundraiser_state.pending_authority,
+            "Wrong pending authority"
+        );
+
+        let previous_authority = fundraiser_state.authority;
+        fundraiser_state.authority = fundraiser_state.pending_authority;
+        fundraiser_state.pending_authority = [0u8; 32];
+
+        previous_authority
+    };
+
+    AuthorityAccepted {
+        fundraiser: *fundraiser.key(),
+        previous_authority,
+        new_authority: *new_authority.key(),
+    }
+    .emit_to(accounts)?;
+
+    Ok(())
+}
@@ -1,150 +1,134 @@
-// use pinocchio::{
-//     account_info::AccountInfo,
-//     instruction::{Seed, Signer},
-//     msg,
-//     pubkey::{self, log},
-//     sysvars::{rent::Rent, Sysvar},
-//     ProgramResult,
-// };
-// use pinocchio_token::instructions::CloseAccount;
-
-// use crate::state::Escrow;
-
-// pub fn process_take_instruction(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
-//     let [taker, maker, maker_ata_a, maker_ata_b, mint_a, mint_b, escrow_account, taker_ata_a, taker_ata_b, escrow_ata, _system_program, _token_program, _associated_token_program, _rent_sysvar @ ..] =
-//         accounts
-//     else {
-//         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
-//     };
-
-//     // check that taker is signer
-
-//     // check that maker is not signer
-
-//     // check that maker and taker mints are correct
-//     let taker_token_account_a =
-//         pinocchio_token::state::TokenAccount::from_account_info(&taker_ata_a).unwrap();
-//     let maker_token_account_a =
-//         pinocchio_token::state::TokenAccount::from_account_info(&maker_ata_a).unwrap();
-//     let taker_token_account_b =
-//         pinocchio_token::state::TokenAccount::from_account_info(&taker_ata_b).unwrap();
-//     let maker_token_account_b =
-//         pinocchio_token::state::TokenAccount::from_account_info(&maker_ata_b).unwrap();
-
-//     let mint_a_account = pinocchio_token::state::Mint::from_account_info(mint_a).unwrap();
-
-//     assert_eq!(
-//         &taker_token_account_a.mint(),
-//         &mint_a.key(),
-//         "Invalid taker_token_account_a"
-//     );
-//     assert_eq!(
-//         &maker_token_account_a.mint(),
-//         &mint_a.key(),
-//         "Invalid maker_token_account_a"
-//     );
-//     assert_eq!(
-//         &taker_token_account_b.mint(),
-//         &mint_b.key(),
-//         "Invalid taker_token_account_a"
-//     );
-//     assert_eq!(
-//         &maker_token_account_b.mint(),
-//         &mint_b.key(),
-//         "Invalid maker_token_account_a"
-//     );
-
-//     // check that maker is the owner of maker_ata provided
-//     assert_eq!(
-//         *maker_token_account_b.owner(),
-//         *maker.key(),
-//         "maker does not own this token"
-//     );
-//     assert_eq!(
-//         *maker_token_account_a.owner(),
-//         *maker.key(),
-//         "maker does not own this token"
-//     );
-//     // check that maker is the creator of the make_ix [make state] and is same in escrow
-//     let escrow_state = Escrow::from_account_info(&escrow_account).unwrap();
-//     assert_eq!(
-//         escrow_state.maker(),
-//         *maker.key(),
-//         "Wrong maker for this escrow"
-//     );
-//     // check that the escrow is owned by this program
-//     assert_eq!(
-//         escrow_account.owner(),
-//         &crate::ID,
-//         "This program does not own the escrow"
-//     );
-
-//     // transfer token to maker_ata
-
-//     // transfer token to taker_ata
-
-//     // close escrow ata
-//     Ok(())
-// }
-
-// pub fn transfer_to_maker(accounts: &[AccountInfo]) -> ProgramResult {
-//     let [taker, _maker, _maker_ata_a, maker_ata_b, _mint_a, mint_b, escrow_account, _taker_ata_a, taker_ata_b, _escrow_ata, _system_program, _token_program, _associated_token_program, _rent_sysvar @ ..] =
-//         accounts
-//     else {
-//         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
-//     };
-
-//     let escrow_state = Escrow::from_account_info(&escrow_account).unwrap();
-
-//     let mint_b_account = pinocchio_token::state::Mint::from_account_info(mint_b).unwrap();
-
-//     pinocchio_token::instructions::TransferChecked {
-//         amount: escrow_state.amount_to_receive(),
-//         authority: taker,
-//         decimals: mint_b_account.decimals(),
-//         from: taker_ata_b,
-//         mint: mint_b,
-//         to: maker_ata_b,
-//     }
-//     .invoke()?;
-//     Ok(())
-// }
-
-// pub fn transfer_to_taker(accounts: &[AccountInfo]) -> ProgramResult {
-//     let [_taker, maker, _maker_ata_a, _maker_ata_b, mint_a, _mint_b, escrow_account, taker_ata_a, _taker_ata_b, escrow_ata, _system_program, _token_program, _associated_token_program, _rent_sysvar @ ..] =
-//         accounts
-//     else {
-//         return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
-//     };
-
-//     let escrow_state = Escrow::from_account_info(&escrow_account).unwrap();
-//     let mint_a_account = pinocchio_token::state::Mint::from_account_info(mint_a).unwrap();
-//     let escrow_bump = [escrow_state.bump];
-//     pinocchio_log::log!("this is the escrow bump: {}", &escrow_bump);
-//     let seed = [
-//         Seed::from(b"escrow"),
-//         Seed::from(maker.key()),
-//         Seed::from(&escrow_bump),
-//     ];
-//     let seeds = Signer::from(&seed);
-
-//     pinocchio_token::instructions::TransferChecked {
-//         amount: escrow_state.amount_to_give(),
-//         authority: escrow_account,
-//         decimals: mint_a_account.decimals(),
-//         from: escrow_ata,
-//         mint: mint_a,
-//         to: taker_ata_a,
-//     }
-//     .invoke_signed(&[seeds])?;
-
-//     // close escrow ata
-//     CloseAccount {
-//         account: escrow_ata,
-//         authority: escrow_account,
-//         destination: maker,
-//     }
-//     .invoke_signed(&[Signer::from(&seed)])?;
-
-//     Ok(())
-// }
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    events::{Claimed, Event},
+    state::{Config, Fundraiser, ProgramAccount},
+};
+
+empty_args! {
+    /// `Claim` and `ExecuteClaim` take no arguments
+    ClaimArgs
+}
+
+instruction_accounts! {
+    /// shared by `Claim` and `ExecuteClaim`, followed by the signing cosigners
+    ClaimAccounts {
+        authority(signer, writable),
+        fundraiser(writable),
+        vault(writable),
+        authority_ata(writable),
+        token_program,
+        config,
+    }
+}
+
+pub fn process_admin_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<ClaimArgs>(data)?;
+
+    claim(accounts, false)
+}
+
+/// Pays the raise out to the authority, either directly (`Claim`) or once a
+/// requested claim sat out its challenge window (`ExecuteClaim`).
+pub fn claim(accounts: &[AccountInfo], timelocked: bool) -> ProgramResult {
+    let (
+        ClaimAccounts {
+            authority,
+            fundraiser,
+            vault,
+            authority_ata,
+            token_program: _,
+            config,
+        },
+        cosigners,
+    ) = ClaimAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    // check that neither the program nor this fundraiser is paused ✅
+    assert!(!Config::load_is_paused(config)?, "Program is paused");
+
+    let (maker, bump, amount) = {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        // check that authority runs this fundraiser ✅
+        assert_eq!(
+            authority.key(),
+            &fundraiser_state.authority,
+            "Wrong authority"
+        );
+        fundraiser_state.assert_cosigned(cosigners);
+
+        let now = Clock::get()?.unix_timestamp;
+        fundraiser_state.assert_claimable(now);
+
+        // check that the claim went through its challenge window, if it has one ✅
+        if timelocked {
+            assert!(
+                fundraiser_state.claim_requested(),
+                "Claim was not requested"
+            );
+            assert!(
+                now >= fundraiser_state.claim_unlocks_at(),
+                "Claim is still timelocked"
+            );
+        } else {
+            assert!(
+                !fundraiser_state.is_timelocked(),
+                "Fundraiser is timelocked, use RequestClaim"
+            );
+        }
+
+        // check that the vault is the one the fundraiser was created with ✅
+        fundraiser_state.assert_vault(vault);
+        let vault_state = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
+
+        let amount = if fundraiser_state.is_oversubscribable() {
+            u64::from_le_bytes(fundraiser_state.amount_to_raise)
+        } else {
+            vault_state.amount()
+        };
+
+        fundraiser_state.claimed = [1];
+
+        (fundraiser_state.maker, fundraiser_state.bump, amount)
+    };
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(&maker),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+
+    Transfer {
+        amount,
+        authority: fundraiser,
+        from: vault,
+        to: authority_ata,
+    }
+    .invoke_signed(&[seeds])?;
+
+    Claimed {
+        fundraiser: *fundraiser.key(),
+        authority: *authority.key(),
+        amount,
+    }
+    .emit_to(accounts)?;
+
+    Ok(())
+}
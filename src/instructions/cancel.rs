@@ -0,0 +1,85 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{Cancelled, Event},
+    state::{Fundraiser, ProgramAccount},
+};
+
+empty_args! {
+    CancelArgs
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    CancelAccounts {
+        authority(signer),
+        fundraiser(writable),
+    }
+}
+
+/// Ends a running campaign early, contributors can then `Refund`.
+pub fn process_cancel(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<CancelArgs>(data)?;
+
+    let (
+        CancelAccounts {
+            authority,
+            fundraiser,
+        },
+        cosigners,
+    ) = CancelAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // check that authority runs this fundraiser ✅
+    assert_eq!(
+        authority.key(),
+        &fundraiser_state.authority,
+        "Wrong authority"
+    );
+    fundraiser_state.assert_cosigned(cosigners);
+
+    // once a campaign ended or paid out, its outcome is final ✅
+    assert!(
+        !fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+        "Fundraiser has ended"
+    );
+    assert!(
+        !fundraiser_state.is_claimed(),
+        "Fundraiser was already claimed"
+    );
+    assert!(
+        !fundraiser_state.is_cancelled(),
+        "Fundraiser was already cancelled"
+    );
+
+    // a disputed campaign is the arbiter's to cancel ✅
+    assert!(
+        !fundraiser_state.is_disputed(),
+        "Fundraiser is under dispute"
+    );
+
+    fundraiser_state.cancelled = [1];
+
+    Cancelled {
+        fundraiser: *fundraiser.key(),
+        authority: *authority.key(),
+    }
+    .emit_to(accounts)?;
+
+    Ok(())
+}
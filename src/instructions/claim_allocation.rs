@@ -0,0 +1,145 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::state::{Config, Contributor, Fundraiser, ProgramAccount};
+
+empty_args! {
+    ClaimAllocationArgs
+}
+
+instruction_accounts! {
+    ClaimAllocationAccounts {
+        contributor(signer, writable),
+        maker,
+        fundraiser(writable),
+        allocation_vault(writable),
+        contributor_allocation_ata(writable),
+        contributor_pda(writable),
+        token_program,
+        config,
+    }
+}
+
+pub fn process_claim_allocation(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<ClaimAllocationArgs>(data)?;
+
+    let (
+        ClaimAllocationAccounts {
+            contributor,
+            maker,
+            fundraiser,
+            allocation_vault,
+            contributor_allocation_ata,
+            contributor_pda,
+            token_program: _,
+            config,
+        },
+        _,
+    ) = ClaimAllocationAccounts::parse(accounts)?;
+
+    // check that contributor is signer ✅
+    assert!(&contributor.is_signer(), "Conributor should be a signer");
+
+    // check that this program owns fundraiser and contributor pda ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &contributor_pda.is_owned_by(&crate::ID),
+        "Contributor account is not owned by this program"
+    );
+
+    // check that neither the program nor this fundraiser is paused ✅
+    assert!(!Config::load_is_paused(config)?, "Program is paused");
+
+    let contributed = {
+        let data = &contributor_pda.try_borrow_data()?;
+        let contributor_state = Contributor::load(data)?;
+        // check that this is the contributor's ledger for this fundraiser ✅
+        contributor_state.assert_ledger_of(fundraiser.key(), contributor.key());
+        u64::from_le_bytes(contributor_state.amount)
+    };
+
+    let (bump, allocation) = {
+        let data = &fundraiser.try_borrow_data()?;
+        let fundraiser_state = Fundraiser::load(data)?;
+
+        assert_eq!(maker.key(), &fundraiser_state.maker, "Wrong maker");
+        assert!(fundraiser_state.is_sale(), "Fundraiser is not a token sale");
+        assert!(!fundraiser_state.is_paused(), "Fundraiser is paused");
+        assert!(!fundraiser_state.is_cancelled(), "Fundraiser was cancelled");
+        assert!(
+            !fundraiser_state.is_disputed(),
+            "Fundraiser is under dispute"
+        );
+
+        // a timelocked raise is only final once its claim went through ✅
+        if fundraiser_state.is_timelocked() {
+            assert!(
+                fundraiser_state.is_claimed(),
+                "Claim has not been executed yet"
+            );
+        }
+
+        assert!(
+            !fundraiser_state.is_oversubscribable(),
+            "Oversubscribed sales are settled with Settle"
+        );
+
+        // allocations unlock once the sale ended and reached its target ✅
+        assert!(
+            fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+            "Fundraiser has not ended yet"
+        );
+        assert!(
+            fundraiser_state.target_reached(),
+            "Fundraiser has not reached its target"
+        );
+
+        // check that the allocation vault belongs to this sale ✅
+        let allocation_vault_state =
+            pinocchio_token::state::TokenAccount::from_account_info(&allocation_vault)?;
+        assert_eq!(
+            allocation_vault_state.owner(),
+            fundraiser.key(),
+            "Illegal Owner of allocation vault"
+        );
+        assert_eq!(
+            allocation_vault_state.mint(),
+            &fundraiser_state.allocation_mint,
+            "Yo!, You provided wrong allocation vault"
+        );
+
+        (
+            fundraiser_state.bump,
+            fundraiser_state.allocation_for(contributed),
+        )
+    };
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(maker.key()),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+
+    Transfer {
+        amount: allocation,
+        authority: fundraiser,
+        from: allocation_vault,
+        to: contributor_allocation_ata,
+    }
+    .invoke_signed(&[seeds])?;
+
+    // close the contributor pda so the allocation can't be claimed twice
+    *contributor.try_borrow_mut_lamports()? += contributor_pda.lamports();
+    contributor_pda.close()?;
+
+    Ok(())
+}
@@ -2,26 +2,88 @@ use bytemuck::{Pod, Zeroable};
 
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{Account, Seed, Signer},
-    log, msg,
-    pubkey::{self, find_program_address, log},
-    sysvars::{self, rent::Rent, Sysvar},
+    instruction::Seed,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 // use pinocchio_log::log;
-use pinocchio_pubkey::derive_address;
-use pinocchio_system::instructions::CreateAccount;
 use pinocchio_token::instructions::Transfer;
 
-use crate::state::{Contributor, Fundraiser};
+use crate::{
+    events::{Contributed, Event},
+    instructions::{create_program_account, split_args},
+    state::{Config, Contributor, Fundraiser, ProgramAccount},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ContributeArgs {
+    pub amount: u64,
+}
+
+impl ContributeArgs {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+/// follows `ContributeArgs` when the fundraiser has an allowlist, and is
+/// itself followed by the merkle proof for `cap`
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct AllowlistArgs {
+    pub cap: u64,
+}
+
+impl AllowlistArgs {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the instructions sysvar when the fundraiser has an attestor
+    ContributeAccounts {
+        contributor(signer, writable),
+        mint,
+        fundraiser(writable),
+        vault(writable),
+        contributor_ata(writable),
+        contributor_pda(writable),
+        system_program,
+        token_program,
+        associated_token_program,
+        rent_sysvar,
+        config,
+    }
+}
+
+impl<T> ContributeAccounts<T> {
+    /// what may follow the named accounts
+    pub const TRAILING: &'static [crate::instructions::AccountSpec] =
+        &[crate::instructions::AccountSpec::new("instructions_sysvar").optional()];
+}
 
 pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [contributor, mint, fundraiser, vault, contributor_ata, contributor_pda, system_program, token_program, associated_token_program, rent_sysvar @ ..] =
-        accounts
-    else {
-        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
-    };
-    let amount = data;
+    let (
+        ContributeAccounts {
+            contributor,
+            mint,
+            fundraiser,
+            vault,
+            contributor_ata,
+            contributor_pda,
+            system_program: _,
+            token_program: _,
+            associated_token_program: _,
+            rent_sysvar: _,
+            config,
+        },
+        remaining_accounts,
+    ) = ContributeAccounts::parse(accounts)?;
+    // `ContributeArgs`, then for allowlisted fundraisers `AllowlistArgs` and the proof
+    let (ContributeArgs { amount }, allowlist_data) = split_args::<ContributeArgs>(data)?;
 
     // check that contributor is signer ✅
     assert!(&contributor.is_signer(), "Conributor should be a signer");
@@ -33,12 +95,15 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         "User derived Wrong Fundraiser we do not own"
     );
 
+    // check that neither the program nor this fundraiser is paused ✅
+    assert!(!Config::load_is_paused(config)?, "Program is paused");
+
     // check that fundraiser exists ✅
-    {
+    let (allowlist_cap, attestor, current_amount) = {
         let data = &mut fundraiser.try_borrow_mut_data()?;
-        let fundraiser_state = &mut bytemuck::from_bytes_mut::<Fundraiser>(data);
+        let fundraiser_state = &mut Fundraiser::load_mut(data)?;
 
-        pinocchio_log::log!("testing this fundraiser state assert 🔥");
+        debug_log!("testing this fundraiser state assert 🔥");
         // check that the mint is correct in fundraiser field ✅
         assert_eq!(
             mint.key(),
@@ -46,45 +111,89 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
             "User Provided Wrong Mint"
         );
 
-        pinocchio_log::log!("did not fail at fundraiser state check 🔥🔥");
-        // check that provided vault is owned by fundraiser state
-        let vault_state = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
-        assert_eq!(
-            vault_state.owner(),
-            fundraiser.key(),
-            "Illegal Owner of Vault"
+        // check that the campaign is still running ✅
+        assert!(
+            !fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+            "Fundraiser has ended"
         );
+        assert!(!fundraiser_state.is_claimed(), "Fundraiser was claimed");
+        assert!(!fundraiser_state.is_cancelled(), "Fundraiser was cancelled");
+        assert!(!fundraiser_state.is_paused(), "Fundraiser is paused");
 
-        pinocchio_log::log!("did not fail at vault_state check 🔥🔥");
+        debug_log!("did not fail at fundraiser state check 🔥🔥");
+        // check that the vault is the one the fundraiser was created with ✅
+        fundraiser_state.assert_vault(vault);
+
+        debug_log!("did not fail at vault_state check 🔥🔥");
         // check that contributor has suffifient amount to transfer
         let contributor_ata_state =
             pinocchio_token::state::TokenAccount::from_account_info(&contributor_ata)?;
 
-        pinocchio_log::log!("user amount: {}", contributor_ata_state.amount());
+        debug_log!("user amount: {}", contributor_ata_state.amount());
 
         assert!(
-            contributor_ata_state.amount() >= u64::from_le_bytes(amount.try_into().unwrap()),
+            contributor_ata_state.amount() >= amount,
             "Insufficient amount to send"
         );
 
         // check that contributor is sending above minimum
         assert!(
-            u64::from_le_bytes(amount.try_into().unwrap()) >= fundraiser_state.min_sendable(),
+            amount >= fundraiser_state.min_sendable(),
             "Insufficient amount to send"
         );
 
         // check that contributor is sending below maximum
         assert!(
-            u64::from_le_bytes(amount.try_into().unwrap()) <= fundraiser_state.max_sendable(),
+            amount <= fundraiser_state.max_sendable(),
             "Insufficient amount to send"
         );
-    }
 
-    // create contributor pda if it's not initialized [init-if-needed]
-    let contributor_seeds: &[&[u8]] = &[b"contributor", contributor.key()];
+        let current_amount = u64::from_le_bytes(fundraiser_state.current_amount) + amount;
+
+        // a sale only holds enough allocation for `amount_to_raise`,
+        // unless it settles the excess pro rata ✅
+        if fundraiser_state.is_sale() && !fundraiser_state.is_oversubscribable() {
+            assert!(
+                current_amount <= u64::from_le_bytes(fundraiser_state.amount_to_raise),
+                "Contribution exceeds the sale cap"
+            );
+        }
+
+        fundraiser_state.current_amount = current_amount.to_le_bytes();
 
-    // let derived_contributor_pda_state = Account::try_from(contributor).unwrap();
-    // // bytemuck::try_pod_read_unaligned::<Contributor>(&derived_contributor_pda).unwrap();
+        // check that contributor is on the allowlist ✅
+        let allowlist_cap = if fundraiser_state.has_allowlist() {
+            let (AllowlistArgs { cap }, proof) = split_args::<AllowlistArgs>(allowlist_data)?;
+            let proof = bytemuck::try_cast_slice::<u8, [u8; 32]>(proof)
+                .map_err(|_| pinocchio::program_error::ProgramError::InvalidInstructionData)?;
+
+            assert!(
+                crate::merkle::verify(
+                    proof,
+                    &fundraiser_state.allowlist_root,
+                    crate::merkle::leaf(contributor.key(), cap),
+                ),
+                "Contributor is not on the allowlist"
+            );
+
+            Some(cap)
+        } else {
+            // nothing may follow the arguments of an open fundraiser ✅
+            if !allowlist_data.is_empty() {
+                return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+            }
+            None
+        };
+
+        let attestor = fundraiser_state
+            .has_attestor()
+            .then_some(fundraiser_state.attestor);
+
+        (allowlist_cap, attestor, current_amount)
+    };
+
+    // create contributor pda if it's not initialized [init-if-needed]
+    let contributor_seeds: &[&[u8]] = &[b"contributor", fundraiser.key(), contributor.key()];
 
     if contributor_pda.lamports() == 0 || contributor_pda.data_is_empty() {
         let (contributor_pda_state, bump) = find_program_address(&contributor_seeds, &crate::ID);
@@ -99,61 +208,102 @@ pub fn process_contribute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         let bump = [initial_bump];
         let seed = [
             Seed::from(b"contributor"),
+            Seed::from(fundraiser.key()),
             Seed::from(contributor.key()),
             Seed::from(&bump),
         ];
-        let seeds = Signer::from(&seed);
-
-        CreateAccount {
-            from: contributor,
-            lamports: Rent::get()?.minimum_balance(Contributor::LEN),
-            owner: &crate::ID,
-            space: Contributor::LEN as u64,
-            to: contributor_pda,
-        }
-        .invoke_signed(&[seeds])?;
+        create_program_account(contributor, contributor_pda, Contributor::LEN, &seed)?;
+        let data = &mut contributor_pda.try_borrow_mut_data()?;
+        let contributor_state = Contributor::init(data)?;
+        contributor_state.fundraiser = *fundraiser.key();
+        contributor_state.wallet = *contributor.key();
+
+        debug_log!("did not fail at this create 🔥🔥");
+    } else {
+        debug_log!("hitting when account already exists 🟩🟩");
+        // Account exists - make sure it is ours before trusting it
+        assert!(
+            &contributor_pda.is_owned_by(&crate::ID),
+            "Contributor account is not owned by this program"
+        );
+    }
 
-        pinocchio_log::log!("did not fail at this create 🔥🔥");
-        // deposit to the vault
+    let contributed = {
+        let data = &contributor_pda.try_borrow_data()?;
+        let contributor_state = Contributor::load(data)?;
+        // check that this is the contributor's ledger for this fundraiser ✅
+        contributor_state.assert_ledger_of(fundraiser.key(), contributor.key());
+        u64::from_le_bytes(contributor_state.amount) + amount
+    };
 
-        Transfer {
-            amount: u64::from_le_bytes(amount.try_into().unwrap()),
-            authority: contributor,
-            from: contributor_ata,
-            to: vault,
-        }
-        .invoke()?;
+    // check that the attestor vouched for this contribution ✅
+    let attestation_nonce = match attestor {
+        Some(attestor) => {
+            let instructions_sysvar = remaining_accounts
+                .first()
+                .ok_or(pinocchio::program_error::ProgramError::NotEnoughAccountKeys)?;
+            let voucher =
+                crate::attestation::load_verified_voucher(instructions_sysvar, &attestor)?;
 
-        // increase contributor amount by how much was deposited
+            assert_eq!(
+                &voucher.fundraiser,
+                fundraiser.key(),
+                "Voucher is for another fundraiser"
+            );
+            assert_eq!(
+                &voucher.wallet,
+                contributor.key(),
+                "Voucher is for another wallet"
+            );
+            assert!(
+                Clock::get()?.unix_timestamp < voucher.expires_at,
+                "Voucher has expired"
+            );
+            assert!(
+                amount <= voucher.max_amount,
+                "Contribution exceeds the voucher"
+            );
 
-        let data = &mut contributor_pda.try_borrow_mut_data()?;
-        let derived_contributor_pda_state = bytemuck::from_bytes_mut::<Contributor>(data);
-        pinocchio_log::log!("did not fail at bytemuck deserialization 🔥");
-        derived_contributor_pda_state.amount =
-            (u64::from_le_bytes(derived_contributor_pda_state.amount)
-                + u64::from_le_bytes(amount.try_into().unwrap()))
-            .to_le_bytes()
-    } else {
-        pinocchio_log::log!("hitting when account already exists 🟩🟩");
-        // Account exists - deserialize it
-        let data = &mut contributor.try_borrow_mut_data()?;
-        let derived_contributor_pda_state = bytemuck::from_bytes_mut::<Contributor>(data);
-
-        // deposit to the vault
-        Transfer {
-            amount: u64::from_le_bytes(amount.try_into().unwrap()),
-            authority: contributor,
-            from: contributor_ata,
-            to: vault,
+            // a voucher is only good once per wallet ✅
+            let data = &contributor_pda.try_borrow_data()?;
+            let last_nonce = u64::from_le_bytes(Contributor::load(data)?.attestation_nonce);
+            assert!(voucher.nonce > last_nonce, "Voucher was already used");
+
+            Some(voucher.nonce)
         }
-        .invoke()?;
+        None => None,
+    };
+
+    // check that contributor stays within their allowlist cap ✅
+    if let Some(cap) = allowlist_cap {
+        assert!(contributed <= cap, "Contribution exceeds the allowlist cap");
+    }
+
+    // deposit to the vault
+    Transfer {
+        amount,
+        authority: contributor,
+        from: contributor_ata,
+        to: vault,
+    }
+    .invoke()?;
+
+    // increase contributor amount by how much was deposited
+    let data = &mut contributor_pda.try_borrow_mut_data()?;
+    let derived_contributor_pda_state = Contributor::load_mut(data)?;
+    debug_log!("did not fail at bytemuck deserialization 🔥");
+    derived_contributor_pda_state.amount = contributed.to_le_bytes();
+    if let Some(nonce) = attestation_nonce {
+        derived_contributor_pda_state.attestation_nonce = nonce.to_le_bytes();
+    }
 
-        // increase contributor amount by how much was deposited
-        derived_contributor_pda_state.amount =
-            (u64::from_le_bytes(derived_contributor_pda_state.amount)
-                + u64::from_le_bytes(amount.try_into().unwrap()))
-            .to_le_bytes()
+    Contributed {
+        fundraiser: *fundraiser.key(),
+        contributor: *contributor.key(),
+        amount,
+        current_amount,
     }
+    .emit_to(accounts)?;
 
     Ok(())
 }
@@ -0,0 +1,81 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::{registry_position, Fundraiser, ProgramAccount, RegistryPage};
+
+empty_args! {
+    DelistArgs
+}
+
+instruction_accounts! {
+    DelistAccounts {
+        fundraiser,
+        registry_page(writable),
+    }
+}
+
+/// Marks a finished campaign closed in the registry. Anyone can call it once
+/// the campaign was claimed, cancelled or ran out of time.
+pub fn process_delist(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<DelistArgs>(data)?;
+
+    let (
+        DelistAccounts {
+            fundraiser,
+            registry_page,
+        },
+        _,
+    ) = DelistAccounts::parse(accounts)?;
+
+    // check that this program owns fundraiser and registry page ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &registry_page.is_owned_by(&crate::ID),
+        "Registry page is not owned by this program"
+    );
+
+    let registry_index = {
+        let data = &fundraiser.try_borrow_data()?;
+        let fundraiser_state = Fundraiser::load(data)?;
+
+        assert!(
+            fundraiser_state.is_claimed()
+                || fundraiser_state.is_cancelled()
+                || fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+            "Fundraiser is still running"
+        );
+
+        u64::from_le_bytes(fundraiser_state.registry_index)
+    };
+
+    // check that the page is the one holding this campaign ✅
+    let (page_index, slot) = registry_position(registry_index);
+    let (registry_page_pda, _) =
+        pubkey::find_program_address(&[b"registry", &page_index.to_le_bytes()], &crate::ID);
+    assert_eq!(
+        registry_page.key(),
+        &registry_page_pda,
+        "You provided the wrong registry page pda"
+    );
+
+    let data = &mut registry_page.try_borrow_mut_data()?;
+    let entry = &mut RegistryPage::load_mut(data)?.entries[slot];
+
+    assert_eq!(
+        &entry.fundraiser,
+        fundraiser.key(),
+        "Registry entry is for another fundraiser"
+    );
+    assert!(!entry.is_closed(), "Fundraiser was already delisted");
+
+    entry.closed = [1];
+
+    Ok(())
+}
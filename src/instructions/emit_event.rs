@@ -0,0 +1,34 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::events::EVENT_AUTHORITY;
+
+instruction_accounts! {
+    EmitEventAccounts {
+        event_authority(signer),
+    }
+}
+
+/// Target of the self-CPI in `Event::emit_to`. It does nothing, the event is
+/// its instruction data. Only the program can sign for the event authority,
+/// so nobody else can forge events through it.
+pub fn process_emit_event(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let (
+        EmitEventAccounts {
+            event_authority: event_authority_account,
+        },
+        _,
+    ) = EmitEventAccounts::parse(accounts)?;
+
+    // check that the program itself signed for its event authority ✅
+    assert_eq!(
+        event_authority_account.key(),
+        &EVENT_AUTHORITY,
+        "You provided the wrong event authority"
+    );
+    assert!(
+        &event_authority_account.is_signer(),
+        "Events can only be emitted by the program"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+pub fn process_execute_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<crate::instructions::ClaimArgs>(data)?;
+
+    crate::instructions::claim(accounts, true)
+}
@@ -0,0 +1,81 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::{Fundraiser, ProgramAccount};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Extend {
+    /// new duration counted from `time_started`, must be longer than the current one
+    pub duration: u64,
+}
+
+impl Extend {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    ExtendAccounts {
+        authority(signer),
+        fundraiser(writable),
+    }
+}
+
+pub fn process_extend(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        ExtendAccounts {
+            authority,
+            fundraiser,
+        },
+        cosigners,
+    ) = ExtendAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let ix_data = crate::instructions::parse_args::<Extend>(data)?;
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // check that authority runs this fundraiser ✅
+    assert_eq!(
+        authority.key(),
+        &fundraiser_state.authority,
+        "Wrong authority"
+    );
+    fundraiser_state.assert_cosigned(cosigners);
+
+    // only a running campaign can be extended, and never shortened ✅
+    assert!(
+        !fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+        "Fundraiser has ended"
+    );
+    assert!(
+        !fundraiser_state.is_claimed(),
+        "Fundraiser was already claimed"
+    );
+    assert!(!fundraiser_state.is_cancelled(), "Fundraiser was cancelled");
+    assert!(
+        ix_data.duration > u64::from_le_bytes(fundraiser_state.duration),
+        "Extension should be longer than the current duration"
+    );
+
+    fundraiser_state.duration = ix_data.duration.to_le_bytes();
+
+    Ok(())
+}
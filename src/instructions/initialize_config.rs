@@ -0,0 +1,99 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    instructions::create_program_account,
+    state::{Config, ProgramAccount},
+};
+
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// `UpgradeableLoaderState::ProgramData` tag, then the last deploy slot and
+/// the upgrade authority as an `Option<Pubkey>`
+const PROGRAM_DATA_TAG: [u8; 4] = 3u32.to_le_bytes();
+const UPGRADE_AUTHORITY_OFFSET: usize = 4 + 8;
+
+empty_args! {
+    InitializeConfigArgs
+}
+
+instruction_accounts! {
+    /// `program_data` is this program's upgradeable loader data account
+    InitializeConfigAccounts {
+        guardian(signer, writable),
+        config(writable),
+        program_data,
+        system_program,
+    }
+}
+
+pub fn process_initialize_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<InitializeConfigArgs>(data)?;
+
+    let (
+        InitializeConfigAccounts {
+            guardian,
+            config,
+            program_data,
+            system_program: _,
+        },
+        _,
+    ) = InitializeConfigAccounts::parse(accounts)?;
+
+    // check that guardian is a signer ✅
+    assert!(&guardian.is_signer(), "Guardian should be a signer");
+
+    // check that guardian is the program's upgrade authority, so whoever
+    // deployed it and not the first caller to get here picks the guardian ✅
+    let (program_data_pda, _) =
+        pubkey::find_program_address(&[crate::ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID);
+    assert_eq!(
+        program_data.key(),
+        &program_data_pda,
+        "You provided the wrong program data account"
+    );
+    assert!(
+        &program_data.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID),
+        "Program data account is not owned by the upgradeable loader"
+    );
+    {
+        let data = program_data.try_borrow_data()?;
+        assert!(
+            data.len() >= UPGRADE_AUTHORITY_OFFSET + 1 + 32
+                && data[..4] == PROGRAM_DATA_TAG
+                && data[UPGRADE_AUTHORITY_OFFSET] == 1,
+            "Program is not upgradeable"
+        );
+        assert_eq!(
+            &data[UPGRADE_AUTHORITY_OFFSET + 1..UPGRADE_AUTHORITY_OFFSET + 1 + 32],
+            guardian.key(),
+            "Guardian should be the upgrade authority"
+        );
+    }
+
+    // check that config derived from client == derived config in program ✅
+    let (config_pda, bump) = pubkey::find_program_address(&[b"config"], &crate::ID);
+    assert_eq!(
+        config.key(),
+        &config_pda,
+        "You provided the wrong config pda"
+    );
+
+    // create config account, fails if it already exists
+    let bump = [bump];
+    let seed = [Seed::from(b"config"), Seed::from(&bump)];
+    create_program_account(guardian, config, Config::LEN, &seed)?;
+
+    let data = &mut config.try_borrow_mut_data()?;
+    let config_state = Config::init(data)?;
+
+    config_state.guardian = *guardian.key();
+    config_state.bump = bump;
+
+    Ok(())
+}
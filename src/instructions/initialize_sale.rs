@@ -0,0 +1,116 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    instructions::{InitializeAccounts, InitializeFundraiser},
+    state::{Fundraiser, ProgramAccount},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct InitializeSale {
+    pub fundraiser: InitializeFundraiser,
+    /// project tokens handed out per `allocation_denominator` raised tokens
+    pub allocation_numerator: u64,
+    pub allocation_denominator: u64,
+    /// non-zero lets contributions go past `amount_to_raise`, settled pro rata
+    pub allow_oversubscription: u64,
+}
+
+impl InitializeSale {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// follows the `InitializeAccounts`
+    InitializeSaleAccounts {
+        allocation_mint,
+        allocation_vault(writable),
+        maker_allocation_ata(writable),
+    }
+}
+
+pub fn process_initialize_sale(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        InitializeAccounts {
+            maker,
+            mint,
+            fundraiser,
+            ..
+        },
+        remaining_accounts,
+    ) = InitializeAccounts::parse(accounts)?;
+    let (
+        InitializeSaleAccounts {
+            allocation_mint,
+            allocation_vault,
+            maker_allocation_ata,
+        },
+        _,
+    ) = InitializeSaleAccounts::parse(remaining_accounts)?;
+
+    // the campaign's metadata follows the sale arguments
+    let (ix_data, metadata_data) = crate::instructions::split_args::<InitializeSale>(data)?;
+
+    // check that the price ratio is usable ✅
+    assert!(
+        ix_data.allocation_numerator > 0 && ix_data.allocation_denominator > 0,
+        "Invalid allocation ratio"
+    );
+
+    // the sale is a regular fundraiser plus an allocation vault
+    crate::instructions::process_intialize_fundraiser(
+        accounts,
+        &[ix_data.fundraiser.to_bytes(), metadata_data.to_vec()].concat(),
+    )?;
+
+    // check fundraiser is authority of allocation vault ✅
+    {
+        let allocation_vault_state =
+            pinocchio_token::state::TokenAccount::from_account_info(&allocation_vault)?;
+        assert_eq!(
+            allocation_vault_state.owner(),
+            fundraiser.key(),
+            "Illegal Owner of allocation vault"
+        );
+        assert_eq!(
+            allocation_vault_state.mint(),
+            allocation_mint.key(),
+            "Yo!, You provided wrong allocation mint address"
+        );
+    }
+
+    // the raise and the sale must not share a mint, or the vaults would mix ✅
+    assert_ne!(
+        allocation_mint.key(),
+        mint.key(),
+        "Allocation mint must differ from the raised mint"
+    );
+
+    let allocation = {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        fundraiser_state.allocation_mint = *allocation_mint.key();
+        fundraiser_state.allocation_numerator = ix_data.allocation_numerator.to_le_bytes();
+        fundraiser_state.allocation_denominator = ix_data.allocation_denominator.to_le_bytes();
+        fundraiser_state.allow_oversubscription = [(ix_data.allow_oversubscription != 0) as u8];
+
+        fundraiser_state.allocation_for(ix_data.fundraiser.amount_to_raise)
+    };
+
+    // deposit the full allocation for `amount_to_raise` up front
+    Transfer {
+        amount: allocation,
+        authority: maker,
+        from: maker_allocation_ata,
+        to: allocation_vault,
+    }
+    .invoke()?;
+
+    Ok(())
+}
@@ -2,23 +2,32 @@ use bytemuck::{Pod, Zeroable};
 
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    instruction::Seed,
     log, msg,
     pubkey::{self, log},
-    sysvars::{self, rent::Rent, Sysvar},
+    sysvars::{self, Sysvar},
     ProgramResult,
 };
 // use pinocchio_log::log;
 use pinocchio_pubkey::derive_address;
-use pinocchio_system::instructions::CreateAccount;
 
-use crate::state::Fundraiser;
+use crate::{
+    events::{Event, FundraiserCreated},
+    instructions::{create_program_account, parse_args, split_args, UpdateMetadata},
+    state::{
+        registry_position, CampaignMetadata, Fundraiser, ProgramAccount, Registry, RegistryPage,
+    },
+};
 
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
 pub struct InitializeFundraiser {
     pub amount_to_raise: u64,
     pub duration: u64,
+    /// seconds a requested claim can be challenged for, 0 lets the authority claim directly
+    pub claim_timelock: u64,
+    /// may block a requested claim, all zeroes for none
+    pub arbiter: [u8; 32],
 }
 
 impl InitializeFundraiser {
@@ -27,19 +36,48 @@ impl InitializeFundraiser {
     }
 }
 
+instruction_accounts! {
+    /// also the first accounts of `InitializeSale`
+    InitializeAccounts {
+        maker(signer, writable),
+        mint,
+        fundraiser(writable),
+        vault,
+        system_program,
+        token_program,
+        associated_token_program,
+        rent_sysvar,
+        metadata(writable),
+        registry(writable),
+        registry_page(writable),
+    }
+}
+
 pub fn process_intialize_fundraiser(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [maker, mint, fundraiser, vault, system_program, token_program, associated_token_program, rent_sysvar @ ..] =
-        accounts
-    else {
-        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
-    };
+    let (
+        InitializeAccounts {
+            maker,
+            mint,
+            fundraiser,
+            vault,
+            system_program: _,
+            token_program: _,
+            associated_token_program: _,
+            rent_sysvar: _,
+            metadata,
+            registry,
+            registry_page,
+        },
+        _,
+    ) = InitializeAccounts::parse(accounts)?;
 
     // checks
     // check that maker is a signer ✅
     assert!(&maker.is_signer(), "Maker should be a signer");
 
-    let ix_data = bytemuck::try_pod_read_unaligned::<InitializeFundraiser>(data)
-        .map_err(|_| pinocchio::program_error::ProgramError::InvalidInstructionData)?;
+    // the campaign's metadata follows its arguments
+    let (ix_data, metadata_data) = split_args::<InitializeFundraiser>(data)?;
+    let metadata_ix_data = parse_args::<UpdateMetadata>(metadata_data)?;
 
     // check that fundraiser derived from client == derived fundraiser in program to see that program id and seeds match ✅
 
@@ -76,40 +114,157 @@ pub fn process_intialize_fundraiser(accounts: &[AccountInfo], data: &[u8]) -> Pr
         Seed::from(maker.key()),
         Seed::from(&bump),
     ];
-    let seeds = Signer::from(&seed);
-    CreateAccount {
-        from: maker,
-        lamports: Rent::get()?.minimum_balance(Fundraiser::LEN),
-        owner: &crate::ID,
-        space: Fundraiser::LEN as u64,
-        to: fundraiser,
+    create_program_account(maker, fundraiser, Fundraiser::LEN, &seed)?;
+
+    {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::init(data)?;
+
+        // let mut fundraiser_state = Fundraiser {
+        //     maker: *maker.key(),
+        //     mint_to_raise: *mint.key(),
+        //     amount_to_raise: ix_data.amount_to_raise.to_le_bytes(),
+        //     current_amount: 0u64.to_le_bytes(),
+        //     time_started: (sysvars::clock::Clock::get()?.unix_timestamp as u64).to_le_bytes(),
+        //     duration: ix_data.duration.to_le_bytes(),
+        //     bump,
+        // };
+
+        // let mut data = fundraiser.try_borrow_mut_data()?;
+        // data[..Fundraiser::LEN].copy_from_slice(bytemuck::bytes_of(&fundraiser_state));
+
+        fundraiser_state.amount_to_raise = ix_data.amount_to_raise.to_le_bytes();
+        fundraiser_state.bump = bump;
+        fundraiser_state.current_amount = 0u64.to_le_bytes();
+        fundraiser_state.duration = ix_data.duration.to_le_bytes();
+        fundraiser_state.claim_timelock = ix_data.claim_timelock.to_le_bytes();
+        fundraiser_state.arbiter = ix_data.arbiter;
+        fundraiser_state.maker = *maker.key();
+        fundraiser_state.authority = *maker.key();
+        fundraiser_state.mint_to_raise = *mint.key();
+        fundraiser_state.vault = *vault.key();
+        fundraiser_state.time_started =
+            (sysvars::clock::Clock::get()?.unix_timestamp as u64).to_le_bytes();
     }
-    .invoke_signed(&[seeds])?;
 
+    // create metadata account next to the fundraiser
+    let (metadata_pda, metadata_bump) =
+        pubkey::find_program_address(&[b"metadata", fundraiser.key()], &crate::ID);
+    assert_eq!(
+        &metadata_pda,
+        metadata.key(),
+        "You provided the wrong metadata pda"
+    );
+
+    let metadata_bump = [metadata_bump];
+    let seed = [
+        Seed::from(b"metadata"),
+        Seed::from(fundraiser.key()),
+        Seed::from(&metadata_bump),
+    ];
+    create_program_account(maker, metadata, CampaignMetadata::LEN, &seed)?;
+
+    let data = &mut metadata.try_borrow_mut_data()?;
+    let metadata_state = CampaignMetadata::init(data)?;
+
+    metadata_state.fundraiser = *fundraiser.key();
+    metadata_state.bump = metadata_bump;
+    metadata_ix_data.write_to(metadata_state)?;
+
+    let registry_index = register(
+        maker,
+        fundraiser,
+        registry,
+        registry_page,
+        metadata_ix_data.category,
+    )?;
     let data = &mut fundraiser.try_borrow_mut_data()?;
-    let fundraiser_state = &mut bytemuck::from_bytes_mut::<Fundraiser>(data);
-
-    // let mut fundraiser_state = Fundraiser {
-    //     maker: *maker.key(),
-    //     mint_to_raise: *mint.key(),
-    //     amount_to_raise: ix_data.amount_to_raise.to_le_bytes(),
-    //     current_amount: 0u64.to_le_bytes(),
-    //     time_started: (sysvars::clock::Clock::get()?.unix_timestamp as u64).to_le_bytes(),
-    //     duration: ix_data.duration.to_le_bytes(),
-    //     bump,
-    // };
-
-    // let mut data = fundraiser.try_borrow_mut_data()?;
-    // data[..Fundraiser::LEN].copy_from_slice(bytemuck::bytes_of(&fundraiser_state));
-
-    fundraiser_state.amount_to_raise = ix_data.amount_to_raise.to_le_bytes();
-    fundraiser_state.bump = bump;
-    fundraiser_state.current_amount = 0u64.to_le_bytes();
-    fundraiser_state.duration = ix_data.duration.to_le_bytes();
-    fundraiser_state.maker = *maker.key();
-    fundraiser_state.mint_to_raise = *mint.key();
-    fundraiser_state.time_started =
-        (sysvars::clock::Clock::get()?.unix_timestamp as u64).to_le_bytes();
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+    fundraiser_state.registry_index = registry_index.to_le_bytes();
+
+    FundraiserCreated {
+        fundraiser: *fundraiser.key(),
+        maker: fundraiser_state.maker,
+        mint_to_raise: fundraiser_state.mint_to_raise,
+        amount_to_raise: ix_data.amount_to_raise,
+        time_started: u64::from_le_bytes(fundraiser_state.time_started) as i64,
+        duration: ix_data.duration,
+    }
+    .emit_to(accounts)?;
 
     Ok(())
 }
+
+/// Appends `fundraiser` to the registry, creating the registry and its next
+/// page on first use, and returns its position. Both addresses are known in
+/// advance, so either may already hold lamports when it is created.
+fn register(
+    maker: &AccountInfo,
+    fundraiser: &AccountInfo,
+    registry: &AccountInfo,
+    registry_page: &AccountInfo,
+    category: u8,
+) -> Result<u64, pinocchio::program_error::ProgramError> {
+    let (registry_pda, registry_bump) = pubkey::find_program_address(&[b"registry"], &crate::ID);
+    assert_eq!(
+        registry.key(),
+        &registry_pda,
+        "You provided the wrong registry pda"
+    );
+
+    if registry.data_is_empty() {
+        let registry_bump = [registry_bump];
+        let seed = [Seed::from(b"registry"), Seed::from(&registry_bump)];
+        create_program_account(maker, registry, Registry::LEN, &seed)?;
+
+        let data = &mut registry.try_borrow_mut_data()?;
+        Registry::init(data)?.bump = registry_bump;
+    }
+    assert!(
+        &registry.is_owned_by(&crate::ID),
+        "Registry account is not owned by this program"
+    );
+
+    let index = {
+        let data = &mut registry.try_borrow_mut_data()?;
+        let registry_state = Registry::load_mut(data)?;
+        let index = u64::from_le_bytes(registry_state.count);
+        registry_state.count = (index + 1).to_le_bytes();
+        index
+    };
+
+    // check that the page is the one the next campaign goes in ✅
+    let (page_index, slot) = registry_position(index);
+    let page_index = page_index.to_le_bytes();
+    let (registry_page_pda, page_bump) =
+        pubkey::find_program_address(&[b"registry", &page_index], &crate::ID);
+    assert_eq!(
+        registry_page.key(),
+        &registry_page_pda,
+        "You provided the wrong registry page pda"
+    );
+
+    if slot == 0 {
+        let page_bump = [page_bump];
+        let seed = [
+            Seed::from(b"registry"),
+            Seed::from(&page_index),
+            Seed::from(&page_bump),
+        ];
+        create_program_account(maker, registry_page, RegistryPage::LEN, &seed)?;
+
+        let data = &mut registry_page.try_borrow_mut_data()?;
+        let page_state = RegistryPage::init(data)?;
+        page_state.index = page_index;
+        page_state.bump = page_bump;
+    }
+
+    let data = &mut registry_page.try_borrow_mut_data()?;
+    let page_state = RegistryPage::load_mut(data)?;
+
+    page_state.entries[slot].fundraiser = *fundraiser.key();
+    page_state.entries[slot].category = [category];
+    page_state.len = [slot as u8 + 1];
+
+    Ok(index)
+}
@@ -0,0 +1,174 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::state::{AccountDiscriminator, Contributor, Fundraiser, ProgramAccount};
+
+empty_args! {
+    /// `Migrate` takes no arguments, the account kind is read from its data
+    MigrateArgs
+}
+
+instruction_accounts! {
+    /// followed by the accounts the migrated kind needs, see `process_migrate`
+    MigrateAccounts {
+        payer(signer, writable),
+        account(writable),
+        system_program,
+    }
+}
+
+/// Upgrades a `Fundraiser` or `Contributor` account from layout version 1.
+///
+/// Version 2 only appends fields, so the account is grown in place, the payer
+/// tops up its rent and the new fields are filled in:
+/// - a fundraiser gets its `vault`, which its authority vouches for with
+///   `[payer, fundraiser, system_program, authority, vault]`
+/// - a ledger gets its `fundraiser` and `wallet`, both checked against its
+///   pda seeds, with `[payer, contributor_pda, system_program, fundraiser, wallet]`
+pub fn process_migrate(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<MigrateArgs>(data)?;
+
+    let (
+        MigrateAccounts {
+            payer,
+            account,
+            system_program: _,
+        },
+        remaining_accounts,
+    ) = MigrateAccounts::parse(accounts)?;
+
+    // check that payer is a signer ✅
+    assert!(&payer.is_signer(), "Payer should be a signer");
+
+    // check that this program owns the account ✅
+    assert!(
+        &account.is_owned_by(&crate::ID),
+        "Account is not owned by this program"
+    );
+
+    let (discriminator, version) = {
+        let data = &account.try_borrow_data()?;
+        match &data[..] {
+            [discriminator, version, ..] => (*discriminator, *version),
+            _ => return Err(pinocchio::program_error::ProgramError::InvalidAccountData),
+        }
+    };
+    assert_eq!(version, 1, "Only version 1 accounts can be migrated");
+
+    if discriminator == AccountDiscriminator::Fundraiser as u8 {
+        migrate_fundraiser(payer, account, remaining_accounts)
+    } else if discriminator == AccountDiscriminator::Contributor as u8 {
+        migrate_contributor(payer, account, remaining_accounts)
+    } else {
+        Err(pinocchio::program_error::ProgramError::InvalidAccountData)
+    }
+}
+
+fn migrate_fundraiser(
+    payer: &AccountInfo,
+    fundraiser: &AccountInfo,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [authority, vault, ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    assert_eq!(
+        fundraiser.data_len(),
+        Fundraiser::V1_LEN,
+        "Unexpected version 1 size"
+    );
+
+    // every version 1 field keeps its offset, so the grown account reads as version 2
+    grow(payer, fundraiser, Fundraiser::LEN)?;
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    data[1] = Fundraiser::VERSION;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // check that authority runs this fundraiser ✅
+    assert_eq!(
+        authority.key(),
+        &fundraiser_state.authority,
+        "Wrong authority"
+    );
+
+    // check that the vault is the fundraiser's vault for the raised mint ✅
+    let vault_state = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
+    assert_eq!(
+        vault_state.owner(),
+        fundraiser.key(),
+        "Illegal Owner of Vault"
+    );
+    assert_eq!(
+        vault_state.mint(),
+        &fundraiser_state.mint_to_raise,
+        "Yo!, You provided wrong vault"
+    );
+
+    fundraiser_state.vault = *vault.key();
+
+    Ok(())
+}
+
+fn migrate_contributor(
+    payer: &AccountInfo,
+    contributor_pda: &AccountInfo,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [fundraiser, wallet, ..] = accounts else {
+        return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+    };
+
+    assert_eq!(
+        contributor_pda.data_len(),
+        Contributor::V1_LEN,
+        "Unexpected version 1 size"
+    );
+
+    // check that the ledger belongs to this fundraiser and wallet ✅
+    let (contributor_pda_state, _) = pubkey::find_program_address(
+        &[b"contributor", fundraiser.key(), wallet.key()],
+        &crate::ID,
+    );
+    assert_eq!(
+        contributor_pda.key(),
+        &contributor_pda_state,
+        "You provided the wrong contributor pda"
+    );
+
+    // every version 1 field keeps its offset, so the grown account reads as version 2
+    grow(payer, contributor_pda, Contributor::LEN)?;
+
+    let data = &mut contributor_pda.try_borrow_mut_data()?;
+    data[1] = Contributor::VERSION;
+    let contributor_state = Contributor::load_mut(data)?;
+    contributor_state.fundraiser = *fundraiser.key();
+    contributor_state.wallet = *wallet.key();
+
+    Ok(())
+}
+
+/// Reallocs `account` to `len` zeroed bytes, the payer covering the extra rent.
+fn grow(payer: &AccountInfo, account: &AccountInfo, len: usize) -> ProgramResult {
+    let rent = Rent::get()?.minimum_balance(len);
+    let lamports = account.lamports();
+    if rent > lamports {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: rent - lamports,
+        }
+        .invoke()?;
+    }
+
+    account.resize(len)
+}
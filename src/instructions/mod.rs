@@ -1,12 +1,114 @@
+/// Declares the accounts an instruction takes, in order, each optionally
+/// marked `(signer, writable)`. The processor parses them with `parse`, the
+/// client builders fill the same struct with `AccountMeta`s and the IDL reads
+/// `ACCOUNTS`, so the order is written down exactly once.
+macro_rules! instruction_accounts {
+    (
+        $(#[$meta:meta])*
+        $name:ident { $($field:ident $(($($flag:ident),*))?),* $(,)? }
+    ) => {
+        $(#[$meta])*
+        pub struct $name<T> {
+            $(pub $field: T,)*
+        }
+
+        impl<'a, T> $name<&'a T> {
+            /// splits the named accounts off the front of `accounts`
+            pub fn parse(
+                accounts: &'a [T],
+            ) -> Result<(Self, &'a [T]), pinocchio::program_error::ProgramError> {
+                let [$($field,)* remaining_accounts @ ..] = accounts else {
+                    return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
+                };
+
+                Ok(($name { $($field,)* }, remaining_accounts))
+            }
+        }
+
+        impl<T> $name<T> {
+            pub const ACCOUNTS: &'static [$crate::instructions::AccountSpec] = &[$(
+                $crate::instructions::AccountSpec::new(stringify!($field)) $($(.$flag())*)?,
+            )*];
+
+            pub fn to_vec(self) -> Vec<T> {
+                vec![$(self.$field,)*]
+            }
+        }
+    };
+}
+
+/// Declares the argument struct of an instruction that takes none. Decoding it
+/// with `parse_args` still rejects stray trailing bytes.
+macro_rules! empty_args {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[repr(C)]
+        #[derive(bytemuck::Pod, bytemuck::Zeroable, Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $name {}
+
+        impl $name {
+            pub fn to_bytes(&self) -> Vec<u8> {
+                Vec::new()
+            }
+        }
+    };
+}
+
+pub mod accept_authority;
 pub mod admin_claim;
+pub mod cancel;
+pub mod claim_allocation;
 pub mod contribute;
+pub mod delist;
+pub mod emit_event;
+pub mod execute_claim;
+pub mod extend;
+pub mod initialize_config;
+pub mod initialize_sale;
 pub mod intialize;
+pub mod migrate;
+pub mod object_claim;
+pub mod open_dispute;
+pub mod propose_authority;
+pub mod reclaim_allocation;
 pub mod refund;
+pub mod request_claim;
+pub mod resolve_dispute;
+pub mod set_allowlist;
+pub mod set_attestor;
+pub mod set_cosigners;
+pub mod set_guardian;
+pub mod set_pause;
+pub mod settle;
+pub mod update_metadata;
 
+pub use accept_authority::*;
 pub use admin_claim::*;
+pub use cancel::*;
+pub use claim_allocation::*;
 pub use contribute::*;
+pub use delist::*;
+pub use emit_event::*;
+pub use execute_claim::*;
+pub use extend::*;
+pub use initialize_config::*;
+pub use initialize_sale::*;
 pub use intialize::*;
+pub use migrate::*;
+pub use object_claim::*;
+pub use open_dispute::*;
+pub use propose_authority::*;
+pub use reclaim_allocation::*;
 pub use refund::*;
+pub use request_claim::*;
+pub use resolve_dispute::*;
+pub use set_allowlist::*;
+pub use set_attestor::*;
+pub use set_cosigners::*;
+pub use set_guardian::*;
+pub use set_pause::*;
+pub use settle::*;
+pub use update_metadata::*;
 
 // #[repr(u8)]
 pub enum FundraisingInstructions {
@@ -14,12 +116,60 @@ pub enum FundraisingInstructions {
     Contribute = 1,
     CheckContributions = 2,
     Refund = 3,
+    Claim = 4,
+    InitializeSale = 5,
+    ClaimAllocation = 6,
+    ReclaimAllocation = 7,
+    Settle = 8,
+    SetAllowlist = 9,
+    SetAttestor = 10,
+    InitializeConfig = 11,
+    SetPause = 12,
+    ProposeAuthority = 13,
+    AcceptAuthority = 14,
+    Cancel = 15,
+    Extend = 16,
+    SetCosigners = 17,
+    RequestClaim = 18,
+    ObjectClaim = 19,
+    ExecuteClaim = 20,
+    OpenDispute = 21,
+    ResolveDispute = 22,
+    UpdateMetadata = 23,
+    Delist = 24,
+    EmitEvent = 25,
+    Migrate = 26,
+    SetGuardian = 27,
 }
 
 // - intialize
 // - contribute
 // - check_contributions
 // - refund
+// - claim
+// - initialize_sale
+// - claim_allocation
+// - reclaim_allocation
+// - settle
+// - set_allowlist
+// - set_attestor
+// - initialize_config
+// - set_pause
+// - propose_authority
+// - accept_authority
+// - cancel
+// - extend
+// - set_cosigners
+// - request_claim
+// - object_claim
+// - execute_claim
+// - open_dispute
+// - resolve_dispute
+// - update_metadata
+// - delist
+// - emit_event
+// - migrate
+// - set_guardian
 impl TryFrom<&u8> for FundraisingInstructions {
     type Error = pinocchio::program_error::ProgramError;
 
@@ -29,7 +179,143 @@ impl TryFrom<&u8> for FundraisingInstructions {
             1 => Ok(FundraisingInstructions::Contribute),
             2 => Ok(FundraisingInstructions::CheckContributions),
             3 => Ok(FundraisingInstructions::Refund),
+            4 => Ok(FundraisingInstructions::Claim),
+            5 => Ok(FundraisingInstructions::InitializeSale),
+            6 => Ok(FundraisingInstructions::ClaimAllocation),
+            7 => Ok(FundraisingInstructions::ReclaimAllocation),
+            8 => Ok(FundraisingInstructions::Settle),
+            9 => Ok(FundraisingInstructions::SetAllowlist),
+            10 => Ok(FundraisingInstructions::SetAttestor),
+            11 => Ok(FundraisingInstructions::InitializeConfig),
+            12 => Ok(FundraisingInstructions::SetPause),
+            13 => Ok(FundraisingInstructions::ProposeAuthority),
+            14 => Ok(FundraisingInstructions::AcceptAuthority),
+            15 => Ok(FundraisingInstructions::Cancel),
+            16 => Ok(FundraisingInstructions::Extend),
+            17 => Ok(FundraisingInstructions::SetCosigners),
+            18 => Ok(FundraisingInstructions::RequestClaim),
+            19 => Ok(FundraisingInstructions::ObjectClaim),
+            20 => Ok(FundraisingInstructions::ExecuteClaim),
+            21 => Ok(FundraisingInstructions::OpenDispute),
+            22 => Ok(FundraisingInstructions::ResolveDispute),
+            23 => Ok(FundraisingInstructions::UpdateMetadata),
+            24 => Ok(FundraisingInstructions::Delist),
+            25 => Ok(FundraisingInstructions::EmitEvent),
+            26 => Ok(FundraisingInstructions::Migrate),
+            27 => Ok(FundraisingInstructions::SetGuardian),
             _ => Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
         }
     }
 }
+
+/// Decodes an instruction's arguments, which must make up all of `data`.
+pub fn parse_args<T: bytemuck::Pod>(
+    data: &[u8],
+) -> Result<T, pinocchio::program_error::ProgramError> {
+    bytemuck::try_pod_read_unaligned::<T>(data)
+        .map_err(|_| pinocchio::program_error::ProgramError::InvalidInstructionData)
+}
+
+/// Decodes the arguments at the front of `data` and returns what follows them,
+/// for instructions with trailing variable length data.
+pub fn split_args<T: bytemuck::Pod>(
+    data: &[u8],
+) -> Result<(T, &[u8]), pinocchio::program_error::ProgramError> {
+    if data.len() < core::mem::size_of::<T>() {
+        return Err(pinocchio::program_error::ProgramError::InvalidInstructionData);
+    }
+    let (args, rest) = data.split_at(core::mem::size_of::<T>());
+
+    Ok((parse_args::<T>(args)?, rest))
+}
+
+/// Creates `account`, a pda of this program that `seeds` sign for, with
+/// `space` bytes. Anyone can send lamports to an address derived from public
+/// seeds before it exists, which makes `CreateAccount` fail, so an account
+/// that already holds some is topped up to rent exemption, allocated and
+/// assigned instead.
+pub fn create_program_account(
+    payer: &pinocchio::account_info::AccountInfo,
+    account: &pinocchio::account_info::AccountInfo,
+    space: usize,
+    seeds: &[pinocchio::instruction::Seed],
+) -> pinocchio::ProgramResult {
+    use pinocchio::sysvars::{rent::Rent, Sysvar};
+    use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+
+    let lamports = Rent::get()?.minimum_balance(space);
+    let signers = [pinocchio::instruction::Signer::from(seeds)];
+
+    if account.lamports() == 0 {
+        return CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&signers);
+    }
+
+    let top_up = lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: top_up,
+        }
+        .invoke()?;
+    }
+    Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(&signers)?;
+    Assign {
+        account,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&signers)
+}
+
+/// An account an instruction takes, see `instruction_accounts!`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccountSpec {
+    pub name: &'static str,
+    pub signer: bool,
+    pub writable: bool,
+    /// only passed when the instruction needs it, after the named accounts
+    pub optional: bool,
+}
+
+impl AccountSpec {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            signer: false,
+            writable: false,
+            optional: false,
+        }
+    }
+
+    pub const fn signer(self) -> Self {
+        Self {
+            signer: true,
+            ..self
+        }
+    }
+
+    pub const fn writable(self) -> Self {
+        Self {
+            writable: true,
+            ..self
+        }
+    }
+
+    pub const fn optional(self) -> Self {
+        Self {
+            optional: true,
+            ..self
+        }
+    }
+}
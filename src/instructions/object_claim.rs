@@ -0,0 +1,114 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{ClaimObjected, Event},
+    state::{Contributor, Fundraiser, ProgramAccount},
+};
+
+empty_args! {
+    ObjectClaimArgs
+}
+
+instruction_accounts! {
+    /// followed by the objector's contributor pda, unless they are the arbiter
+    ObjectClaimAccounts {
+        objector(signer),
+        fundraiser(writable),
+    }
+}
+
+impl<T> ObjectClaimAccounts<T> {
+    /// what may follow the named accounts
+    pub const TRAILING: &'static [crate::instructions::AccountSpec] =
+        &[crate::instructions::AccountSpec::new("contributor_pda")
+            .writable()
+            .optional()];
+}
+
+/// Objects to a requested claim. The arbiter blocks it outright, contributors
+/// block it once objections cover more than half of `current_amount`.
+pub fn process_object_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<ObjectClaimArgs>(data)?;
+
+    let (
+        ObjectClaimAccounts {
+            objector,
+            fundraiser,
+        },
+        remaining_accounts,
+    ) = ObjectClaimAccounts::parse(accounts)?;
+
+    // check that objector is a signer ✅
+    assert!(&objector.is_signer(), "Objector should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // objections only count inside the challenge window ✅
+    assert!(
+        fundraiser_state.claim_requested(),
+        "Claim was not requested"
+    );
+    assert!(
+        Clock::get()?.unix_timestamp < fundraiser_state.claim_unlocks_at(),
+        "Challenge window has closed"
+    );
+    assert!(
+        !fundraiser_state.is_claimed(),
+        "Fundraiser was already claimed"
+    );
+
+    if fundraiser_state.arbiter != [0u8; 32] && objector.key() == &fundraiser_state.arbiter {
+        fundraiser_state.claim_blocked = [1];
+
+        return ClaimObjected {
+            fundraiser: *fundraiser.key(),
+            objector: *objector.key(),
+            objections: fundraiser_state.objections,
+            claim_blocked: 1,
+        }
+        .emit_to(remaining_accounts);
+    }
+
+    // otherwise the objector votes with what they contributed
+    let contributor_pda = remaining_accounts
+        .first()
+        .ok_or(pinocchio::program_error::ProgramError::NotEnoughAccountKeys)?;
+    assert!(
+        &contributor_pda.is_owned_by(&crate::ID),
+        "Contributor account is not owned by this program"
+    );
+
+    let data = &mut contributor_pda.try_borrow_mut_data()?;
+    let contributor_state = Contributor::load_mut(data)?;
+    contributor_state.assert_ledger_of(fundraiser.key(), objector.key());
+
+    assert!(contributor_state.objected[0] == 0, "Already objected");
+    contributor_state.objected = [1];
+
+    let objections = u64::from_le_bytes(fundraiser_state.objections)
+        + u64::from_le_bytes(contributor_state.amount);
+    fundraiser_state.objections = objections.to_le_bytes();
+
+    if objections as u128 * 2 > u64::from_le_bytes(fundraiser_state.current_amount) as u128 {
+        fundraiser_state.claim_blocked = [1];
+    }
+
+    ClaimObjected {
+        fundraiser: *fundraiser.key(),
+        objector: *objector.key(),
+        objections: fundraiser_state.objections,
+        claim_blocked: fundraiser_state.claim_blocked[0],
+    }
+    .emit_to(remaining_accounts)
+}
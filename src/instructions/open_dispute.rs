@@ -0,0 +1,150 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{DisputeOpened, Event},
+    instructions::create_program_account,
+    state::{Contributor, Dispute, Fundraiser, ProgramAccount},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct OpenDispute {
+    pub reason: u8,
+}
+
+impl OpenDispute {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the opener's contributor pda, unless they run the campaign
+    OpenDisputeAccounts {
+        opener(signer, writable),
+        fundraiser(writable),
+        dispute(writable),
+        system_program,
+    }
+}
+
+impl<T> OpenDisputeAccounts<T> {
+    /// what may follow the named accounts
+    pub const TRAILING: &'static [crate::instructions::AccountSpec] =
+        &[crate::instructions::AccountSpec::new("contributor_pda").optional()];
+}
+
+/// Escalates a campaign to its arbiter. The maker, the authority or any
+/// contributor can open it, and claims and refunds stay frozen until the
+/// arbiter resolves it, or for at most `DISPUTE_TIMEOUT`, after which the
+/// first `Refund` cancels the campaign.
+pub fn process_open_dispute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        OpenDisputeAccounts {
+            opener,
+            fundraiser,
+            dispute,
+            system_program: _,
+        },
+        remaining_accounts,
+    ) = OpenDisputeAccounts::parse(accounts)?;
+
+    // check that opener is a signer ✅
+    assert!(&opener.is_signer(), "Opener should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let ix_data = crate::instructions::parse_args::<OpenDispute>(data)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        assert!(fundraiser_state.has_arbiter(), "Fundraiser has no arbiter");
+
+        // only an undecided campaign can be disputed ✅
+        assert!(
+            !fundraiser_state.is_claimed(),
+            "Fundraiser was already claimed"
+        );
+        assert!(
+            !fundraiser_state.is_cancelled(),
+            "Fundraiser was already cancelled"
+        );
+        assert!(
+            !fundraiser_state.is_disputed(),
+            "Fundraiser is already under dispute"
+        );
+        // once refunds are open a dispute would only hold contributors' money back ✅
+        assert!(
+            !fundraiser_state.is_refundable(now),
+            "Fundraiser is already refundable"
+        );
+
+        // check that the opener has a stake in the campaign ✅
+        if opener.key() != &fundraiser_state.maker && opener.key() != &fundraiser_state.authority {
+            let contributor_pda = remaining_accounts
+                .first()
+                .ok_or(pinocchio::program_error::ProgramError::NotEnoughAccountKeys)?;
+            assert!(
+                &contributor_pda.is_owned_by(&crate::ID),
+                "Contributor account is not owned by this program"
+            );
+            let ledger = &contributor_pda.try_borrow_data()?;
+            Contributor::load(ledger)?.assert_ledger_of(fundraiser.key(), opener.key());
+        }
+
+        fundraiser_state.disputed = [1];
+    }
+
+    // check that dispute derived from client == derived dispute in program ✅
+    let (dispute_pda, bump) =
+        pubkey::find_program_address(&[b"dispute", fundraiser.key()], &crate::ID);
+    assert_eq!(
+        dispute.key(),
+        &dispute_pda,
+        "You provided the wrong dispute pda"
+    );
+
+    // create dispute account, one per fundraiser
+    let bump = [bump];
+    let seed = [
+        Seed::from(b"dispute"),
+        Seed::from(fundraiser.key()),
+        Seed::from(&bump),
+    ];
+    create_program_account(opener, dispute, Dispute::LEN, &seed)?;
+
+    {
+        let data = &mut dispute.try_borrow_mut_data()?;
+        let dispute_state = Dispute::init(data)?;
+
+        dispute_state.fundraiser = *fundraiser.key();
+        dispute_state.opened_by = *opener.key();
+        dispute_state.reason = [ix_data.reason];
+        dispute_state.opened_at = (now as u64).to_le_bytes();
+        dispute_state.bump = bump;
+    }
+
+    DisputeOpened {
+        fundraiser: *fundraiser.key(),
+        opened_by: *opener.key(),
+        reason: ix_data.reason,
+    }
+    .emit_to(remaining_accounts)?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    events::{AuthorityProposed, Event},
+    state::{Fundraiser, ProgramAccount},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ProposeAuthority {
+    /// all zeroes withdraws a pending proposal
+    pub new_authority: [u8; 32],
+}
+
+impl ProposeAuthority {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    ProposeAuthorityAccounts {
+        authority(signer),
+        fundraiser(writable),
+    }
+}
+
+pub fn process_propose_authority(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        ProposeAuthorityAccounts {
+            authority,
+            fundraiser,
+        },
+        cosigners,
+    ) = ProposeAuthorityAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let ix_data = crate::instructions::parse_args::<ProposeAuthority>(data)?;
+
+    {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        // check that authority runs this fundraiser ✅
+        assert_eq!(
+            authority.key(),
+            &fundraiser_state.authority,
+            "Wrong authority"
+        );
+        fundraiser_state.assert_cosigned(cosigners);
+
+        // nothing changes until the new authority accepts
+        fundraiser_state.pending_authority = ix_data.new_authority;
+    }
+
+    AuthorityProposed {
+        fundraiser: *fundraiser.key(),
+        authority: *authority.key(),
+        new_authority: ix_data.new_authority,
+    }
+    .emit_to(cosigners)?;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::state::{Fundraiser, ProgramAccount};
+
+empty_args! {
+    ReclaimAllocationArgs
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    ReclaimAllocationAccounts {
+        authority(signer),
+        fundraiser(writable),
+        allocation_vault(writable),
+        authority_allocation_ata(writable),
+        token_program,
+    }
+}
+
+pub fn process_reclaim_allocation(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<ReclaimAllocationArgs>(data)?;
+
+    let (
+        ReclaimAllocationAccounts {
+            authority,
+            fundraiser,
+            allocation_vault,
+            authority_allocation_ata,
+            token_program: _,
+        },
+        cosigners,
+    ) = ReclaimAllocationAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let (maker, bump, amount) = {
+        let data = &fundraiser.try_borrow_data()?;
+        let fundraiser_state = Fundraiser::load(data)?;
+
+        assert_eq!(
+            authority.key(),
+            &fundraiser_state.authority,
+            "Wrong authority"
+        );
+        fundraiser_state.assert_cosigned(cosigners);
+        assert!(fundraiser_state.is_sale(), "Fundraiser is not a token sale");
+
+        assert!(
+            !fundraiser_state.is_disputed(),
+            "Fundraiser is under dispute"
+        );
+
+        // the maker only gets the allocation back when the sale failed ✅
+        assert!(
+            fundraiser_state.is_refundable(Clock::get()?.unix_timestamp),
+            "Fundraiser did not fail"
+        );
+
+        // check that the allocation vault belongs to this sale ✅
+        let allocation_vault_state =
+            pinocchio_token::state::TokenAccount::from_account_info(&allocation_vault)?;
+        assert_eq!(
+            allocation_vault_state.owner(),
+            fundraiser.key(),
+            "Illegal Owner of allocation vault"
+        );
+        assert_eq!(
+            allocation_vault_state.mint(),
+            &fundraiser_state.allocation_mint,
+            "Yo!, You provided wrong allocation vault"
+        );
+
+        (
+            fundraiser_state.maker,
+            fundraiser_state.bump,
+            allocation_vault_state.amount(),
+        )
+    };
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(&maker),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+
+    Transfer {
+        amount,
+        authority: fundraiser,
+        from: allocation_vault,
+        to: authority_allocation_ata,
+    }
+    .invoke_signed(&[seeds])?;
+
+    Ok(())
+}
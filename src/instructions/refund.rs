@@ -0,0 +1,184 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    events::{Cancelled, DisputeResolved, Event, Refunded, EVENT_AUTHORITY},
+    state::{Contributor, Dispute, Fundraiser, ProgramAccount, Resolution},
+};
+
+empty_args! {
+    RefundArgs
+}
+
+instruction_accounts! {
+    RefundAccounts {
+        contributor(signer, writable),
+        maker,
+        fundraiser(writable),
+        vault(writable),
+        contributor_ata(writable),
+        contributor_pda(writable),
+        token_program,
+    }
+}
+
+impl<T> RefundAccounts<T> {
+    /// what may follow the named accounts
+    pub const TRAILING: &'static [crate::instructions::AccountSpec] =
+        &[crate::instructions::AccountSpec::new("dispute").optional()];
+}
+
+pub fn process_refund(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<RefundArgs>(data)?;
+
+    let (
+        RefundAccounts {
+            contributor,
+            maker,
+            fundraiser,
+            vault,
+            contributor_ata,
+            contributor_pda,
+            token_program: _,
+        },
+        remaining_accounts,
+    ) = RefundAccounts::parse(accounts)?;
+
+    // check that contributor is signer ✅
+    assert!(&contributor.is_signer(), "Conributor should be a signer");
+
+    // check that this program owns fundraiser and contributor pda ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &contributor_pda.is_owned_by(&crate::ID),
+        "Contributor account is not owned by this program"
+    );
+
+    let amount = {
+        let data = &contributor_pda.try_borrow_data()?;
+        let contributor_state = Contributor::load(data)?;
+        // check that this is the contributor's ledger for this fundraiser ✅
+        contributor_state.assert_ledger_of(fundraiser.key(), contributor.key());
+        u64::from_le_bytes(contributor_state.amount)
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let (bump, refund, current_amount, lapsed) = {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        assert_eq!(maker.key(), &fundraiser_state.maker, "Wrong maker");
+
+        // check that the vault is the one the fundraiser was created with ✅
+        fundraiser_state.assert_vault(vault);
+
+        // the arbiter decides what a disputed campaign pays back,
+        // unless they let the dispute lapse, which cancels the campaign ✅
+        let lapsed = fundraiser_state.is_disputed();
+        if lapsed {
+            let dispute = remaining_accounts
+                .first()
+                .filter(|account| account.key() != &EVENT_AUTHORITY)
+                .ok_or(pinocchio::program_error::ProgramError::NotEnoughAccountKeys)?;
+            lapse_dispute(fundraiser, dispute, now)?;
+
+            fundraiser_state.disputed = [0];
+            fundraiser_state.cancelled = [1];
+        }
+
+        // refunds only open once the campaign was cancelled,
+        // or ended without reaching its target ✅
+        assert!(
+            fundraiser_state.is_refundable(now),
+            "Fundraiser is not refundable"
+        );
+
+        fundraiser_state.current_amount = u64::from_le_bytes(fundraiser_state.current_amount)
+            .saturating_sub(amount)
+            .to_le_bytes();
+
+        (
+            fundraiser_state.bump,
+            fundraiser_state.refund_for(amount),
+            u64::from_le_bytes(fundraiser_state.current_amount),
+            lapsed,
+        )
+    };
+
+    if lapsed {
+        DisputeResolved {
+            fundraiser: *fundraiser.key(),
+            arbiter: [0; 32],
+            resolution: Resolution::Refund as u8,
+            maker_share_bps: [0; 2],
+        }
+        .emit_to(accounts)?;
+        Cancelled {
+            fundraiser: *fundraiser.key(),
+            authority: [0; 32],
+        }
+        .emit_to(accounts)?;
+    }
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(maker.key()),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+
+    Transfer {
+        amount: refund,
+        authority: fundraiser,
+        from: vault,
+        to: contributor_ata,
+    }
+    .invoke_signed(&[seeds])?;
+
+    // close the contributor pda so the refund can't be taken twice
+    *contributor.try_borrow_mut_lamports()? += contributor_pda.lamports();
+    contributor_pda.close()?;
+
+    Refunded {
+        fundraiser: *fundraiser.key(),
+        contributor: *contributor.key(),
+        amount: refund,
+        current_amount,
+    }
+    .emit_to(accounts)?;
+
+    Ok(())
+}
+
+/// Records `dispute` as refunded once the arbiter's time to rule ran out.
+fn lapse_dispute(fundraiser: &AccountInfo, dispute: &AccountInfo, now: i64) -> ProgramResult {
+    assert!(
+        &dispute.is_owned_by(&crate::ID),
+        "Dispute account is not owned by this program"
+    );
+
+    let data = &mut dispute.try_borrow_mut_data()?;
+    let dispute_state = Dispute::load_mut(data)?;
+
+    assert_eq!(
+        &dispute_state.fundraiser,
+        fundraiser.key(),
+        "Dispute is for another fundraiser"
+    );
+    assert!(dispute_state.has_lapsed(now), "Fundraiser is under dispute");
+
+    dispute_state.resolved = [1];
+    dispute_state.resolution = [Resolution::Refund as u8];
+    dispute_state.resolved_at = (now as u64).to_le_bytes();
+
+    Ok(())
+}
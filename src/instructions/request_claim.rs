@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::{Config, Fundraiser, ProgramAccount};
+
+empty_args! {
+    RequestClaimArgs
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    RequestClaimAccounts {
+        authority(signer),
+        fundraiser(writable),
+        config,
+    }
+}
+
+/// Opens the challenge window of a timelocked fundraiser.
+pub fn process_request_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<RequestClaimArgs>(data)?;
+
+    let (
+        RequestClaimAccounts {
+            authority,
+            fundraiser,
+            config,
+        },
+        cosigners,
+    ) = RequestClaimAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    // check that neither the program nor this fundraiser is paused ✅
+    assert!(!Config::load_is_paused(config)?, "Program is paused");
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // check that authority runs this fundraiser ✅
+    assert_eq!(
+        authority.key(),
+        &fundraiser_state.authority,
+        "Wrong authority"
+    );
+    fundraiser_state.assert_cosigned(cosigners);
+
+    let now = Clock::get()?.unix_timestamp;
+    fundraiser_state.assert_claimable(now);
+
+    assert!(
+        fundraiser_state.is_timelocked(),
+        "Fundraiser is not timelocked, use Claim"
+    );
+    assert!(
+        !fundraiser_state.claim_requested(),
+        "Claim was already requested"
+    );
+
+    fundraiser_state.claim_requested_at = (now as u64).to_le_bytes();
+
+    Ok(())
+}
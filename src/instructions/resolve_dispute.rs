@@ -0,0 +1,231 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    events::{Cancelled, Claimed, DisputeResolved, Event},
+    state::{Config, Dispute, Fundraiser, ProgramAccount, Resolution, BPS_DENOMINATOR},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ResolveDispute {
+    /// a `Resolution`
+    pub resolution: u8,
+    /// authority's share of the raise in a `Split`, ignored otherwise
+    pub maker_share_bps: [u8; 2],
+}
+
+impl ResolveDispute {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    ResolveDisputeAccounts {
+        arbiter(signer),
+        fundraiser(writable),
+        dispute(writable),
+        vault(writable),
+        authority_ata(writable),
+        token_program,
+        config,
+    }
+}
+
+/// The arbiter's ruling on an open dispute: release the raise to the
+/// authority, refund everyone, or split the raise between them. A release
+/// still needs the target reached, and an oversubscribed sale to have ended,
+/// and a ruling that pays the authority waits out a pause like a claim does.
+pub fn process_resolve_dispute(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        ResolveDisputeAccounts {
+            arbiter,
+            fundraiser,
+            dispute,
+            vault,
+            authority_ata,
+            token_program: _,
+            config,
+        },
+        _,
+    ) = ResolveDisputeAccounts::parse(accounts)?;
+
+    // check that arbiter is a signer ✅
+    assert!(&arbiter.is_signer(), "Arbiter should be a signer");
+
+    // check that this program owns fundraiser and dispute ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &dispute.is_owned_by(&crate::ID),
+        "Dispute account is not owned by this program"
+    );
+
+    let ix_data = crate::instructions::parse_args::<ResolveDispute>(data)?;
+    let resolution = Resolution::try_from(&ix_data.resolution)?;
+
+    let maker_share_bps = match resolution {
+        Resolution::Release => BPS_DENOMINATOR as u16,
+        Resolution::Refund => 0,
+        Resolution::Split => {
+            let maker_share_bps = u16::from_le_bytes(ix_data.maker_share_bps);
+            assert!(
+                maker_share_bps > 0 && (maker_share_bps as u64) < BPS_DENOMINATOR,
+                "Split should give both sides a share"
+            );
+            maker_share_bps
+        }
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let program_paused = Config::load_is_paused(config)?;
+
+    {
+        let data = &mut dispute.try_borrow_mut_data()?;
+        let dispute_state = Dispute::load_mut(data)?;
+
+        assert_eq!(
+            &dispute_state.fundraiser,
+            fundraiser.key(),
+            "Dispute is for another fundraiser"
+        );
+        assert!(!dispute_state.is_resolved(), "Dispute was already resolved");
+        assert!(!dispute_state.has_lapsed(now), "Dispute has lapsed");
+
+        dispute_state.resolved = [1];
+        dispute_state.resolution = [ix_data.resolution];
+        dispute_state.maker_share_bps = maker_share_bps.to_le_bytes();
+        dispute_state.resolved_at = (now as u64).to_le_bytes();
+    }
+
+    let (maker, bump, amount, authority) = {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        // check that arbiter was named for this fundraiser ✅
+        assert!(fundraiser_state.has_arbiter(), "Fundraiser has no arbiter");
+        assert_eq!(arbiter.key(), &fundraiser_state.arbiter, "Wrong arbiter");
+        assert!(
+            fundraiser_state.is_disputed(),
+            "Fundraiser is not under dispute"
+        );
+
+        // check that the vault is the one the fundraiser was created with ✅
+        fundraiser_state.assert_vault(vault);
+        let vault_state = pinocchio_token::state::TokenAccount::from_account_info(&vault)?;
+
+        fundraiser_state.disputed = [0];
+
+        let amount = match resolution {
+            Resolution::Release => {
+                // the arbiter only releases what a claim could take ✅
+                assert!(
+                    fundraiser_state.target_reached(),
+                    "Fundraiser has not reached its target"
+                );
+                if fundraiser_state.is_oversubscribable() {
+                    assert!(
+                        fundraiser_state.has_ended(now),
+                        "Fundraiser has not ended yet"
+                    );
+                }
+
+                fundraiser_state.claimed = [1];
+
+                // an oversubscribed sale keeps the excess in the vault for `Settle`
+                if fundraiser_state.is_oversubscribable() {
+                    u64::from_le_bytes(fundraiser_state.amount_to_raise).min(vault_state.amount())
+                } else {
+                    vault_state.amount()
+                }
+            }
+            Resolution::Refund | Resolution::Split => {
+                fundraiser_state.cancelled = [1];
+                fundraiser_state.maker_share_bps = maker_share_bps.to_le_bytes();
+
+                // rounded down, `refund_for` leaves contributors the rest
+                (u64::from_le_bytes(fundraiser_state.current_amount) as u128
+                    * maker_share_bps as u128
+                    / BPS_DENOMINATOR as u128) as u64
+            }
+        };
+
+        // check that neither the program nor this fundraiser is paused before paying out ✅
+        if amount > 0 {
+            assert!(!program_paused, "Program is paused");
+            assert!(!fundraiser_state.is_paused(), "Fundraiser is paused");
+        }
+
+        (
+            fundraiser_state.maker,
+            fundraiser_state.bump,
+            amount,
+            fundraiser_state.authority,
+        )
+    };
+
+    DisputeResolved {
+        fundraiser: *fundraiser.key(),
+        arbiter: *arbiter.key(),
+        resolution: ix_data.resolution,
+        maker_share_bps: maker_share_bps.to_le_bytes(),
+    }
+    .emit_to(accounts)?;
+    // refunds reopen unless the whole raise was released
+    if !matches!(resolution, Resolution::Release) {
+        Cancelled {
+            fundraiser: *fundraiser.key(),
+            authority: *arbiter.key(),
+        }
+        .emit_to(accounts)?;
+    }
+
+    if amount == 0 {
+        return Ok(());
+    }
+
+    Claimed {
+        fundraiser: *fundraiser.key(),
+        authority,
+        amount,
+    }
+    .emit_to(accounts)?;
+
+    // pays the authority's share to the authority's token account
+    {
+        let authority_ata_state =
+            pinocchio_token::state::TokenAccount::from_account_info(&authority_ata)?;
+        assert_eq!(
+            authority_ata_state.owner(),
+            &authority,
+            "Payout should go to the authority"
+        );
+    }
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(&maker),
+        Seed::from(&bump),
+    ];
+    let seeds = Signer::from(&seed);
+
+    Transfer {
+        amount,
+        authority: fundraiser,
+        from: vault,
+        to: authority_ata,
+    }
+    .invoke_signed(&[seeds])?;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::state::{Fundraiser, ProgramAccount};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct SetAllowlist {
+    /// all zeroes opens the fundraiser to everyone
+    pub root: [u8; 32],
+}
+
+impl SetAllowlist {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    SetAllowlistAccounts {
+        authority(signer),
+        fundraiser(writable),
+    }
+}
+
+pub fn process_set_allowlist(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        SetAllowlistAccounts {
+            authority,
+            fundraiser,
+        },
+        cosigners,
+    ) = SetAllowlistAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let ix_data = crate::instructions::parse_args::<SetAllowlist>(data)?;
+
+    let data = &mut fundraiser.try_borrow_mut_data()?;
+    let fundraiser_state = Fundraiser::load_mut(data)?;
+
+    // check that authority runs this fundraiser ✅
+    assert_eq!(
+        authority.key(),
+        &fundraiser_state.authority,
+        "Wrong authority"
+    );
+    fundraiser_state.assert_cosigned(cosigners);
+
+    // the allowlist is fixed once the first contribution lands ✅
+    assert_eq!(
+        u64::from_le_bytes(fundraiser_state.current_amount),
+        0,
+        "Fundraiser already started"
+    );
+
+    fundraiser_state.allowlist_root = ix_data.root;
+
+    Ok(())
+}
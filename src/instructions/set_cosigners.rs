@@ -0,0 +1,100 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    events::{CosignersSet, Event},
+    state::{Fundraiser, ProgramAccount, MAX_COSIGNERS},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct SetCosigners {
+    /// 0 turns the multisig off
+    pub threshold: u8,
+    pub cosigner_count: u8,
+    pub cosigners: [[u8; 32]; MAX_COSIGNERS],
+}
+
+impl SetCosigners {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    SetCosignersAccounts {
+        authority(signer),
+        fundraiser(writable),
+    }
+}
+
+/// Replaces the cosigner set, signed off by the current one.
+pub fn process_set_cosigners(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        SetCosignersAccounts {
+            authority,
+            fundraiser,
+        },
+        cosigners,
+    ) = SetCosignersAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+
+    let ix_data = crate::instructions::parse_args::<SetCosigners>(data)?;
+
+    // check that the new set can actually reach its threshold ✅
+    assert!(
+        (ix_data.cosigner_count as usize) <= MAX_COSIGNERS,
+        "Too many cosigners"
+    );
+    let new_cosigners = &ix_data.cosigners[..ix_data.cosigner_count as usize];
+    assert!(
+        ix_data.threshold <= ix_data.cosigner_count,
+        "Threshold is higher than the cosigner count"
+    );
+    assert!(
+        new_cosigners
+            .iter()
+            .enumerate()
+            .all(|(i, cosigner)| !new_cosigners[..i].contains(cosigner)),
+        "Duplicate cosigner"
+    );
+
+    {
+        let data = &mut fundraiser.try_borrow_mut_data()?;
+        let fundraiser_state = Fundraiser::load_mut(data)?;
+
+        // check that authority runs this fundraiser ✅
+        assert_eq!(
+            authority.key(),
+            &fundraiser_state.authority,
+            "Wrong authority"
+        );
+        // the current set has to agree to its replacement ✅
+        fundraiser_state.assert_cosigned(cosigners);
+
+        fundraiser_state.threshold = [ix_data.threshold];
+        fundraiser_state.cosigner_count = [ix_data.cosigner_count];
+        fundraiser_state.cosigners = ix_data.cosigners;
+    }
+
+    CosignersSet {
+        fundraiser: *fundraiser.key(),
+        authority: *authority.key(),
+        threshold: ix_data.threshold,
+        cosigner_count: ix_data.cosigner_count,
+        cosigners: ix_data.cosigners,
+    }
+    .emit_to(cosigners)?;
+
+    Ok(())
+}
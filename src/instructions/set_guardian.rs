@@ -0,0 +1,50 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::state::{Config, ProgramAccount};
+
+empty_args! {
+    SetGuardianArgs
+}
+
+instruction_accounts! {
+    /// both guardians sign, so a mistyped key can't lock the program out
+    SetGuardianAccounts {
+        guardian(signer),
+        new_guardian(signer),
+        config(writable),
+    }
+}
+
+/// Hands the guardian role over to `new_guardian`.
+pub fn process_set_guardian(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<SetGuardianArgs>(data)?;
+
+    let (
+        SetGuardianAccounts {
+            guardian,
+            new_guardian,
+            config,
+        },
+        _,
+    ) = SetGuardianAccounts::parse(accounts)?;
+
+    // check that both guardians are signers ✅
+    assert!(&guardian.is_signer(), "Guardian should be a signer");
+    assert!(&new_guardian.is_signer(), "New guardian should be a signer");
+
+    // check that this program owns config ✅
+    assert!(
+        &config.is_owned_by(&crate::ID),
+        "Config account is not owned by this program"
+    );
+
+    let data = &mut config.try_borrow_mut_data()?;
+    let config_state = Config::load_mut(data)?;
+
+    // check that the guardian is the one in config ✅
+    assert_eq!(guardian.key(), &config_state.guardian, "Wrong guardian");
+
+    config_state.guardian = *new_guardian.key();
+
+    Ok(())
+}
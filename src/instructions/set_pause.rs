@@ -0,0 +1,114 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{Event, Paused, Unpaused, EVENT_AUTHORITY},
+    state::{Config, Fundraiser, ProgramAccount},
+};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct SetPause {
+    /// 1 pauses, 0 unpauses
+    pub paused: u8,
+    pub reason: u8,
+}
+
+impl SetPause {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+instruction_accounts! {
+    /// followed by the fundraiser to pause, if only one, then optionally the
+    /// event authority
+    SetPauseAccounts {
+        guardian(signer),
+        config(writable),
+    }
+}
+
+impl<T> SetPauseAccounts<T> {
+    /// what may follow the named accounts
+    pub const TRAILING: &'static [crate::instructions::AccountSpec] =
+        &[crate::instructions::AccountSpec::new("fundraiser")
+            .writable()
+            .optional()];
+}
+
+/// Pauses or unpauses a single fundraiser when one is passed, the whole program otherwise.
+pub fn process_set_pause(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (SetPauseAccounts { guardian, config }, remaining_accounts) =
+        SetPauseAccounts::parse(accounts)?;
+
+    // check that guardian is a signer ✅
+    assert!(&guardian.is_signer(), "Guardian should be a signer");
+
+    // check that this program owns config ✅
+    assert!(
+        &config.is_owned_by(&crate::ID),
+        "Config account is not owned by this program"
+    );
+
+    let ix_data = crate::instructions::parse_args::<SetPause>(data)?;
+    assert!(ix_data.paused <= 1, "Invalid pause flag");
+
+    let now = Clock::get()?.unix_timestamp.to_le_bytes();
+
+    let data = &mut config.try_borrow_mut_data()?;
+    let config_state = Config::load_mut(data)?;
+
+    // check that the guardian is the one in config ✅
+    assert_eq!(guardian.key(), &config_state.guardian, "Wrong guardian");
+
+    let fundraiser = remaining_accounts
+        .first()
+        .filter(|account| account.key() != &EVENT_AUTHORITY);
+    match fundraiser {
+        Some(fundraiser) => {
+            assert!(
+                &fundraiser.is_owned_by(&crate::ID),
+                "User derived Wrong Fundraiser we do not own"
+            );
+
+            let data = &mut fundraiser.try_borrow_mut_data()?;
+            let fundraiser_state = Fundraiser::load_mut(data)?;
+
+            fundraiser_state.paused = [ix_data.paused];
+            fundraiser_state.pause_reason = [ix_data.reason];
+            fundraiser_state.paused_at = now;
+        }
+        None => {
+            config_state.paused = [ix_data.paused];
+            config_state.pause_reason = [ix_data.reason];
+            config_state.paused_at = now;
+        }
+    }
+
+    let fundraiser = fundraiser.map_or([0; 32], |fundraiser| *fundraiser.key());
+    if ix_data.paused == 1 {
+        Paused {
+            fundraiser,
+            guardian: *guardian.key(),
+            reason: ix_data.reason,
+            paused_at: now,
+        }
+        .emit_to(remaining_accounts)?;
+    } else {
+        Unpaused {
+            fundraiser,
+            guardian: *guardian.key(),
+            reason: ix_data.reason,
+            unpaused_at: now,
+        }
+        .emit_to(remaining_accounts)?;
+    }
+
+    Ok(())
+}
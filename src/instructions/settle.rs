@@ -0,0 +1,161 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::state::{Config, Contributor, Fundraiser, ProgramAccount};
+
+empty_args! {
+    SettleArgs
+}
+
+instruction_accounts! {
+    SettleAccounts {
+        contributor(signer, writable),
+        maker,
+        fundraiser(writable),
+        vault(writable),
+        contributor_ata(writable),
+        allocation_vault(writable),
+        contributor_allocation_ata(writable),
+        contributor_pda(writable),
+        token_program,
+        config,
+    }
+}
+
+pub fn process_settle(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    crate::instructions::parse_args::<SettleArgs>(data)?;
+
+    let (
+        SettleAccounts {
+            contributor,
+            maker,
+            fundraiser,
+            vault,
+            contributor_ata,
+            allocation_vault,
+            contributor_allocation_ata,
+            contributor_pda,
+            token_program: _,
+            config,
+        },
+        _,
+    ) = SettleAccounts::parse(accounts)?;
+
+    // check that contributor is signer ✅
+    assert!(&contributor.is_signer(), "Conributor should be a signer");
+
+    // check that this program owns fundraiser and contributor pda ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &contributor_pda.is_owned_by(&crate::ID),
+        "Contributor account is not owned by this program"
+    );
+
+    // check that neither the program nor this fundraiser is paused ✅
+    assert!(!Config::load_is_paused(config)?, "Program is paused");
+
+    let contributed = {
+        let data = &contributor_pda.try_borrow_data()?;
+        let contributor_state = Contributor::load(data)?;
+        // check that this is the contributor's ledger for this fundraiser ✅
+        contributor_state.assert_ledger_of(fundraiser.key(), contributor.key());
+        u64::from_le_bytes(contributor_state.amount)
+    };
+
+    let (bump, excess, allocation) = {
+        let data = &fundraiser.try_borrow_data()?;
+        let fundraiser_state = Fundraiser::load(data)?;
+
+        assert_eq!(maker.key(), &fundraiser_state.maker, "Wrong maker");
+        assert!(fundraiser_state.is_sale(), "Fundraiser is not a token sale");
+        assert!(!fundraiser_state.is_paused(), "Fundraiser is paused");
+        assert!(!fundraiser_state.is_cancelled(), "Fundraiser was cancelled");
+        assert!(
+            !fundraiser_state.is_disputed(),
+            "Fundraiser is under dispute"
+        );
+
+        // a timelocked raise is only final once its claim went through ✅
+        if fundraiser_state.is_timelocked() {
+            assert!(
+                fundraiser_state.is_claimed(),
+                "Claim has not been executed yet"
+            );
+        }
+
+        assert!(
+            fundraiser_state.is_oversubscribable(),
+            "Capped sales are claimed with ClaimAllocation"
+        );
+
+        // settlement opens once the sale ended and reached its target ✅
+        assert!(
+            fundraiser_state.has_ended(Clock::get()?.unix_timestamp),
+            "Fundraiser has not ended yet"
+        );
+        assert!(
+            fundraiser_state.target_reached(),
+            "Fundraiser has not reached its target"
+        );
+
+        // check that both vaults belong to this sale ✅
+        fundraiser_state.assert_vault(vault);
+
+        let allocation_vault_state =
+            pinocchio_token::state::TokenAccount::from_account_info(&allocation_vault)?;
+        assert_eq!(
+            allocation_vault_state.owner(),
+            fundraiser.key(),
+            "Illegal Owner of allocation vault"
+        );
+        assert_eq!(
+            allocation_vault_state.mint(),
+            &fundraiser_state.allocation_mint,
+            "Yo!, You provided wrong allocation vault"
+        );
+
+        let (excess, allocation) = fundraiser_state.settlement_for(contributed);
+
+        (fundraiser_state.bump, excess, allocation)
+    };
+
+    let seed = [
+        Seed::from(b"fundraiser"),
+        Seed::from(maker.key()),
+        Seed::from(&bump),
+    ];
+
+    // hand out the accepted share as allocation
+    Transfer {
+        amount: allocation,
+        authority: fundraiser,
+        from: allocation_vault,
+        to: contributor_allocation_ata,
+    }
+    .invoke_signed(&[Signer::from(&seed)])?;
+
+    // and return whatever was not accepted
+    if excess > 0 {
+        Transfer {
+            amount: excess,
+            authority: fundraiser,
+            from: vault,
+            to: contributor_ata,
+        }
+        .invoke_signed(&[Signer::from(&seed)])?;
+    }
+
+    // close the contributor pda so the settlement can't be taken twice
+    *contributor.try_borrow_mut_lamports()? += contributor_pda.lamports();
+    contributor_pda.close()?;
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use bytemuck::{Pod, Zeroable};
+
+use pinocchio::{account_info::AccountInfo, pubkey, ProgramResult};
+
+use crate::state::{
+    CampaignMetadata, Category, Fundraiser, ProgramAccount, MAX_TITLE_LEN, MAX_URI_LEN,
+};
+
+/// Metadata fields, sent to `UpdateMetadata` and after the arguments of
+/// `Initialize` and `InitializeSale`.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct UpdateMetadata {
+    pub title_len: u8,
+    pub title: [u8; MAX_TITLE_LEN],
+    pub uri_len: u8,
+    pub uri: [u8; MAX_URI_LEN],
+    /// a `Category`
+    pub category: u8,
+    pub image_hash: [u8; 32],
+}
+
+impl UpdateMetadata {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+
+    /// Checks the fields are well formed and copies them into `metadata`.
+    pub fn write_to(&self, metadata: &mut CampaignMetadata) -> ProgramResult {
+        Category::try_from(&self.category)?;
+
+        let title_len = self.title_len as usize;
+        let uri_len = self.uri_len as usize;
+        assert!(
+            title_len > 0 && title_len <= MAX_TITLE_LEN,
+            "Title length out of bounds"
+        );
+        assert!(uri_len <= MAX_URI_LEN, "URI length out of bounds");
+
+        // nothing but padding past the lengths, so what explorers show is all there is ✅
+        assert!(
+            core::str::from_utf8(&self.title[..title_len]).is_ok()
+                && self.title[title_len..].iter().all(|byte| *byte == 0),
+            "Title should be zero padded utf-8"
+        );
+        assert!(
+            core::str::from_utf8(&self.uri[..uri_len]).is_ok()
+                && self.uri[uri_len..].iter().all(|byte| *byte == 0),
+            "URI should be zero padded utf-8"
+        );
+
+        metadata.title_len = [self.title_len];
+        metadata.title = self.title;
+        metadata.uri_len = [self.uri_len];
+        metadata.uri = self.uri;
+        metadata.category = [self.category];
+        metadata.image_hash = self.image_hash;
+
+        Ok(())
+    }
+}
+
+instruction_accounts! {
+    /// followed by the signing cosigners
+    UpdateMetadataAccounts {
+        authority(signer),
+        fundraiser,
+        metadata(writable),
+    }
+}
+
+pub fn process_update_metadata(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (
+        UpdateMetadataAccounts {
+            authority,
+            fundraiser,
+            metadata,
+        },
+        cosigners,
+    ) = UpdateMetadataAccounts::parse(accounts)?;
+
+    // check that authority is a signer ✅
+    assert!(&authority.is_signer(), "Authority should be a signer");
+
+    // check that this program owns fundraiser and metadata ✅
+    assert!(
+        &fundraiser.is_owned_by(&crate::ID),
+        "User derived Wrong Fundraiser we do not own"
+    );
+    assert!(
+        &metadata.is_owned_by(&crate::ID),
+        "Metadata account is not owned by this program"
+    );
+
+    let ix_data = crate::instructions::parse_args::<UpdateMetadata>(data)?;
+
+    {
+        let data = &fundraiser.try_borrow_data()?;
+        let fundraiser_state = Fundraiser::load(data)?;
+
+        // check that authority runs this fundraiser ✅
+        assert_eq!(
+            authority.key(),
+            &fundraiser_state.authority,
+            "Wrong authority"
+        );
+        fundraiser_state.assert_cosigned(cosigners);
+
+        // backers keep seeing what they backed ✅
+        assert_eq!(
+            u64::from_le_bytes(fundraiser_state.current_amount),
+            0,
+            "Fundraiser already started"
+        );
+    }
+
+    // check that metadata derived from client == derived metadata in program ✅
+    let (metadata_pda, _) =
+        pubkey::find_program_address(&[b"metadata", fundraiser.key()], &crate::ID);
+    assert_eq!(
+        metadata.key(),
+        &metadata_pda,
+        "You provided the wrong metadata pda"
+    );
+
+    let data = &mut metadata.try_borrow_mut_data()?;
+    ix_data.write_to(CampaignMetadata::load_mut(data)?)
+}
@@ -1,12 +1,30 @@
-use pinocchio::{account_info::AccountInfo, entrypoint, pubkey::Pubkey, ProgramResult};
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, ProgramResult};
 
 use crate::instructions::FundraisingInstructions;
 
-mod instructions;
-mod state;
+/// `pinocchio_log::log!` that only exists in builds with the `debug-logs` feature.
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "debug-logs")]
+        pinocchio_log::log!($($arg)*);
+    };
+}
+
+pub mod attestation;
+#[cfg(any(test, feature = "client"))]
+pub mod client;
+pub mod events;
+#[cfg(any(test, feature = "idl"))]
+pub mod idl;
+pub mod instructions;
+pub mod merkle;
+pub mod state;
+#[cfg(any(test, feature = "test-kit"))]
+pub mod test_kit;
 mod tests;
 
-entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::entrypoint!(process_instruction);
 
 pinocchio_pubkey::declare_id!("27abzM8KfWuiYyiy6T3Dv1EeJWSPuBK7DDjtBQoapEfP");
 
@@ -23,12 +41,60 @@ pub fn process_instruction(
 
     match FundraisingInstructions::try_from(discriminator)? {
         FundraisingInstructions::Initialize => {
-            pinocchio_log::log!("got to entry");
+            debug_log!("got to entry");
             instructions::process_intialize_fundraiser(accounts, data)?
         }
         FundraisingInstructions::Contribute => instructions::process_contribute(accounts, data)?,
         // FundraisingInstructions::CheckContributions => {}
-        // FundraisingInstructions::Refund => {}
+        FundraisingInstructions::Refund => instructions::process_refund(accounts, data)?,
+        FundraisingInstructions::Claim => instructions::process_admin_claim(accounts, data)?,
+        FundraisingInstructions::InitializeSale => {
+            instructions::process_initialize_sale(accounts, data)?
+        }
+        FundraisingInstructions::ClaimAllocation => {
+            instructions::process_claim_allocation(accounts, data)?
+        }
+        FundraisingInstructions::ReclaimAllocation => {
+            instructions::process_reclaim_allocation(accounts, data)?
+        }
+        FundraisingInstructions::Settle => instructions::process_settle(accounts, data)?,
+        FundraisingInstructions::SetAllowlist => {
+            instructions::process_set_allowlist(accounts, data)?
+        }
+        FundraisingInstructions::SetAttestor => instructions::process_set_attestor(accounts, data)?,
+        FundraisingInstructions::InitializeConfig => {
+            instructions::process_initialize_config(accounts, data)?
+        }
+        FundraisingInstructions::SetPause => instructions::process_set_pause(accounts, data)?,
+        FundraisingInstructions::ProposeAuthority => {
+            instructions::process_propose_authority(accounts, data)?
+        }
+        FundraisingInstructions::AcceptAuthority => {
+            instructions::process_accept_authority(accounts, data)?
+        }
+        FundraisingInstructions::Cancel => instructions::process_cancel(accounts, data)?,
+        FundraisingInstructions::Extend => instructions::process_extend(accounts, data)?,
+        FundraisingInstructions::SetCosigners => {
+            instructions::process_set_cosigners(accounts, data)?
+        }
+        FundraisingInstructions::RequestClaim => {
+            instructions::process_request_claim(accounts, data)?
+        }
+        FundraisingInstructions::ObjectClaim => instructions::process_object_claim(accounts, data)?,
+        FundraisingInstructions::ExecuteClaim => {
+            instructions::process_execute_claim(accounts, data)?
+        }
+        FundraisingInstructions::OpenDispute => instructions::process_open_dispute(accounts, data)?,
+        FundraisingInstructions::ResolveDispute => {
+            instructions::process_resolve_dispute(accounts, data)?
+        }
+        FundraisingInstructions::UpdateMetadata => {
+            instructions::process_update_metadata(accounts, data)?
+        }
+        FundraisingInstructions::Delist => instructions::process_delist(accounts, data)?,
+        FundraisingInstructions::EmitEvent => instructions::process_emit_event(accounts, data)?,
+        FundraisingInstructions::Migrate => instructions::process_migrate(accounts, data)?,
+        FundraisingInstructions::SetGuardian => instructions::process_set_guardian(accounts, data)?,
         // FundraisingInstructions::MakeV2 => instructions::process_make_instruction_v2(accounts, data)?,
         _ => return Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
     }
@@ -0,0 +1,109 @@
+//! Allowlist merkle tree shared by the program and off-chain tooling.
+//!
+//! Leaves are `sha256(0x00 || wallet || cap)` with `cap` as little endian bytes,
+//! inner nodes are `sha256(0x01 || min(a, b) || max(a, b))`. Sorting each pair
+//! means a proof is just the list of sibling hashes from leaf to root.
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    let mut hash_result = [0u8; 32];
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_sha256(
+            vals as *const _ as *const u8,
+            vals.len() as u64,
+            &mut hash_result as *mut _ as *mut u8,
+        );
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hash_result.copy_from_slice(&hasher.finalize());
+    }
+
+    hash_result
+}
+
+pub fn leaf(wallet: &[u8; 32], cap: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet, &cap.to_le_bytes()])
+}
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[NODE_PREFIX, a, b])
+    } else {
+        hashv(&[NODE_PREFIX, b, a])
+    }
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+
+    &computed == root
+}
+
+/// Builds an allowlist tree off-chain and hands out the proofs `Contribute` expects.
+#[cfg(not(target_os = "solana"))]
+pub struct MerkleTree {
+    // levels[0] holds the leaves, the last level holds the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl MerkleTree {
+    pub fn new(allowlist: &[([u8; 32], u64)]) -> Self {
+        assert!(!allowlist.is_empty(), "Allowlist should not be empty");
+
+        let mut levels = vec![allowlist
+            .iter()
+            .map(|(wallet, cap)| leaf(wallet, *cap))
+            .collect::<Vec<_>>()];
+
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    // an odd node out is carried up unchanged
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        MerkleTree { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// sibling hashes for the `index`-th entry of the allowlist
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        let mut index = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
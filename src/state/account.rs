@@ -0,0 +1,52 @@
+use bytemuck::Pod;
+use pinocchio::program_error::ProgramError;
+
+/// Type tags every account starts with, 0 is left out so a freshly
+/// allocated account never passes as any of them.
+pub enum AccountDiscriminator {
+    Fundraiser = 1,
+    Contributor = 2,
+    Config = 3,
+    Dispute = 4,
+    CampaignMetadata = 5,
+    Registry = 6,
+    RegistryPage = 7,
+}
+
+/// An account owned by this program. Its first byte is its discriminator and
+/// its second its layout version, both checked before the data is cast.
+pub trait ProgramAccount: Pod {
+    const DISCRIMINATOR: AccountDiscriminator;
+    const VERSION: u8;
+
+    fn check_header(data: &[u8]) -> Result<(), ProgramError> {
+        match data {
+            [discriminator, ..] if *discriminator != Self::DISCRIMINATOR as u8 => {
+                Err(ProgramError::InvalidAccountData)
+            }
+            [_, version, ..] if *version == Self::VERSION => Ok(()),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+        bytemuck::try_from_bytes(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+        bytemuck::try_from_bytes_mut(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Tags a just created account, which must still be all zeroes.
+    fn init(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() < 2 || data[0] != 0 {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        data[0] = Self::DISCRIMINATOR as u8;
+        data[1] = Self::VERSION;
+
+        Self::load_mut(data)
+    }
+}
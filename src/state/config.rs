@@ -0,0 +1,57 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::state::{AccountDiscriminator, ProgramAccount};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey};
+
+/// Program-wide settings, a single PDA at `["config"]`.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub guardian: [u8; 32],
+    pub paused: [u8; 1],
+    pub pause_reason: [u8; 1],
+    pub paused_at: [u8; 8],
+    pub bump: [u8; 1],
+}
+
+impl ProgramAccount for Config {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Config;
+    const VERSION: u8 = 1;
+}
+
+impl Config {
+    pub const LEN: usize = core::mem::size_of::<Config>();
+
+    pub fn is_paused(&self) -> bool {
+        self.paused[0] == 1
+    }
+
+    /// Whether the whole program is paused. A config that was never
+    /// initialized has never been paused.
+    pub fn load_is_paused(config: &AccountInfo) -> Result<bool, ProgramError> {
+        let (config_pda, _) = pubkey::find_program_address(&[b"config"], &crate::ID);
+        assert_eq!(
+            config.key(),
+            &config_pda,
+            "You provided the wrong config pda"
+        );
+
+        if config.data_is_empty() {
+            return Ok(false);
+        }
+        assert!(
+            &config.is_owned_by(&crate::ID),
+            "Config account is not owned by this program"
+        );
+
+        let data = &config.try_borrow_data()?;
+        Ok(Config::load(data)?.is_paused())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
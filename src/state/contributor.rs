@@ -1,13 +1,43 @@
 use bytemuck::{Pod, Zeroable};
 
+use pinocchio::pubkey::Pubkey;
+
+use crate::state::{AccountDiscriminator, ProgramAccount};
+
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
 pub struct Contributor {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
     pub amount: [u8; 8],
+    // highest attestation voucher nonce used so far
+    pub attestation_nonce: [u8; 8],
+    // whether this contributor already objected to a claim
+    pub objected: [u8; 1],
+    // added in layout version 2, so a ledger says whose it is
+    pub fundraiser: [u8; 32],
+    pub wallet: [u8; 32],
+}
+
+impl ProgramAccount for Contributor {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Contributor;
+    const VERSION: u8 = 2;
 }
 
 impl Contributor {
     pub const LEN: usize = core::mem::size_of::<Contributor>();
+    /// layout version 1 stopped before `fundraiser`
+    pub const V1_LEN: usize = Self::LEN - 64;
+
+    /// Checks that this is `wallet`'s ledger for `fundraiser`, the seeds its
+    /// address was derived from, without deriving it again.
+    pub fn assert_ledger_of(&self, fundraiser: &Pubkey, wallet: &Pubkey) {
+        assert!(
+            &self.fundraiser == fundraiser && &self.wallet == wallet,
+            "You provided the wrong contributor pda"
+        );
+    }
 
     pub fn to_bytes(&self) -> Vec<u8> {
         bytemuck::bytes_of(self).to_vec()
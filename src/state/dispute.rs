@@ -0,0 +1,78 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::state::{AccountDiscriminator, ProgramAccount};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// seconds the arbiter has to rule before a dispute lapses into a refund
+pub const DISPUTE_TIMEOUT: i64 = 30 * 24 * 60 * 60;
+
+/// An escalation to the fundraiser's arbiter, a PDA at `["dispute", fundraiser]`.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Dispute {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub fundraiser: [u8; 32],
+    pub opened_by: [u8; 32],
+    // picked by the opener, the program only records it
+    pub reason: [u8; 1],
+    pub opened_at: [u8; 8],
+    pub resolved: [u8; 1],
+    // a `Resolution`, only meaningful once resolved
+    pub resolution: [u8; 1],
+    pub maker_share_bps: [u8; 2],
+    pub resolved_at: [u8; 8],
+    pub bump: [u8; 1],
+}
+
+impl ProgramAccount for Dispute {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Dispute;
+    const VERSION: u8 = 1;
+}
+
+impl Dispute {
+    pub const LEN: usize = core::mem::size_of::<Dispute>();
+
+    pub fn is_resolved(&self) -> bool {
+        self.resolved[0] == 1
+    }
+
+    /// unix timestamp from which the arbiter can no longer rule
+    pub fn deadline(&self) -> i64 {
+        (u64::from_le_bytes(self.opened_at) as i64).saturating_add(DISPUTE_TIMEOUT)
+    }
+
+    pub fn has_lapsed(&self, now: i64) -> bool {
+        now >= self.deadline()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+/// How the arbiter settles a dispute.
+pub enum Resolution {
+    /// the raise goes to the authority as if it had been claimed
+    Release = 0,
+    /// the campaign is cancelled and every contributor gets their money back
+    Refund = 1,
+    /// the authority gets `maker_share_bps` of the raise now, contributors
+    /// refund the rest
+    Split = 2,
+}
+
+impl TryFrom<&u8> for Resolution {
+    type Error = pinocchio::program_error::ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Resolution::Release),
+            1 => Ok(Resolution::Refund),
+            2 => Ok(Resolution::Split),
+            _ => Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
+        }
+    }
+}
@@ -1,8 +1,16 @@
 use bytemuck::{Pod, Zeroable};
 
+use crate::state::{AccountDiscriminator, ProgramAccount, BPS_DENOMINATOR};
+use pinocchio::account_info::AccountInfo;
+
+pub const MAX_COSIGNERS: usize = 5;
+
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
 pub struct Fundraiser {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
     pub maker: [u8; 32],
     pub mint_to_raise: [u8; 32],
     pub amount_to_raise: [u8; 8],
@@ -10,10 +18,53 @@ pub struct Fundraiser {
     pub time_started: [u8; 8],
     pub duration: [u8; 8],
     pub bump: [u8; 1],
+    // token sale mode, all zeroes for a plain fundraiser
+    pub allocation_mint: [u8; 32],
+    pub allocation_numerator: [u8; 8],
+    pub allocation_denominator: [u8; 8],
+    pub allow_oversubscription: [u8; 1],
+    pub claimed: [u8; 1],
+    // merkle root of (wallet, cap) leaves, all zeroes for an open fundraiser
+    pub allowlist_root: [u8; 32],
+    // signer of compliance vouchers, all zeroes when none are required
+    pub attestor: [u8; 32],
+    // set by the guardian in `Config`
+    pub paused: [u8; 1],
+    pub pause_reason: [u8; 1],
+    pub paused_at: [u8; 8],
+    // `maker` stays the pda seed, `authority` runs the campaign
+    pub authority: [u8; 32],
+    pub pending_authority: [u8; 32],
+    pub cancelled: [u8; 1],
+    // admin actions need `threshold` of the first `cosigner_count` cosigners, none when 0
+    pub threshold: [u8; 1],
+    pub cosigner_count: [u8; 1],
+    pub cosigners: [[u8; 32]; MAX_COSIGNERS],
+    // claims wait out `claim_timelock` seconds after `RequestClaim`, none when 0
+    pub claim_timelock: [u8; 8],
+    pub claim_requested_at: [u8; 8],
+    pub arbiter: [u8; 32],
+    pub objections: [u8; 8],
+    pub claim_blocked: [u8; 1],
+    // set while a `Dispute` awaits the arbiter, freezes claims and refunds
+    pub disputed: [u8; 1],
+    // share of the raise the arbiter awarded the authority in a split
+    pub maker_share_bps: [u8; 2],
+    // position in the campaign registry, see `registry_position`
+    pub registry_index: [u8; 8],
+    // added in layout version 2
+    pub vault: [u8; 32],
+}
+
+impl ProgramAccount for Fundraiser {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Fundraiser;
+    const VERSION: u8 = 2;
 }
 
 impl Fundraiser {
     pub const LEN: usize = core::mem::size_of::<Fundraiser>();
+    /// layout version 1 stopped before `vault`
+    pub const V1_LEN: usize = Self::LEN - 32;
 
     pub fn max_sendable(&self) -> u64 {
         10_000_000_000
@@ -22,6 +73,174 @@ impl Fundraiser {
         10_000_000
     } // 10 usd
 
+    /// unix timestamp from which contributions are no longer accepted
+    pub fn deadline(&self) -> i64 {
+        u64::from_le_bytes(self.time_started).saturating_add(u64::from_le_bytes(self.duration))
+            as i64
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.deadline()
+    }
+
+    pub fn target_reached(&self) -> bool {
+        u64::from_le_bytes(self.current_amount) >= u64::from_le_bytes(self.amount_to_raise)
+    }
+
+    pub fn is_sale(&self) -> bool {
+        self.allocation_mint != [0u8; 32]
+    }
+
+    pub fn is_oversubscribable(&self) -> bool {
+        self.allow_oversubscription[0] == 1
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.claimed[0] == 1
+    }
+
+    pub fn has_allowlist(&self) -> bool {
+        self.allowlist_root != [0u8; 32]
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled[0] == 1
+    }
+
+    /// contributors get their money back once the campaign is cancelled, its
+    /// claim was blocked, or it ended without reaching its target, unless the
+    /// arbiter released the raise to the authority
+    pub fn is_refundable(&self, now: i64) -> bool {
+        !self.is_claimed()
+            && (self.is_cancelled()
+                || self.is_claim_blocked()
+                || (self.has_ended(now) && !self.target_reached()))
+    }
+
+    pub fn is_timelocked(&self) -> bool {
+        u64::from_le_bytes(self.claim_timelock) > 0
+    }
+
+    pub fn claim_requested(&self) -> bool {
+        u64::from_le_bytes(self.claim_requested_at) > 0
+    }
+
+    /// end of the challenge window opened by `RequestClaim`
+    pub fn claim_unlocks_at(&self) -> i64 {
+        u64::from_le_bytes(self.claim_requested_at)
+            .saturating_add(u64::from_le_bytes(self.claim_timelock)) as i64
+    }
+
+    pub fn is_claim_blocked(&self) -> bool {
+        self.claim_blocked[0] == 1
+    }
+
+    pub fn has_arbiter(&self) -> bool {
+        self.arbiter != [0u8; 32]
+    }
+
+    pub fn is_disputed(&self) -> bool {
+        self.disputed[0] == 1
+    }
+
+    /// what refunding `amount` pays back, less the authority's share of a split,
+    /// rounded down so the refunds never exceed what the split left in the vault
+    pub fn refund_for(&self, amount: u64) -> u64 {
+        let contributor_share_bps =
+            BPS_DENOMINATOR - u16::from_le_bytes(self.maker_share_bps) as u64;
+
+        (amount as u128 * contributor_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Checks shared by every path that pays the raise out to the authority.
+    pub fn assert_claimable(&self, now: i64) {
+        assert!(!self.is_paused(), "Fundraiser is paused");
+        assert!(!self.is_disputed(), "Fundraiser is under dispute");
+        assert!(!self.is_cancelled(), "Fundraiser was cancelled");
+        assert!(!self.is_claim_blocked(), "Claim was blocked");
+
+        // check that the target was reached ✅
+        assert!(
+            self.target_reached(),
+            "Fundraiser has not reached its target"
+        );
+        assert!(!self.is_claimed(), "Fundraiser was already claimed");
+
+        // an oversubscribed sale leaves the excess in the vault for `Settle`,
+        // so it can only be claimed once the total is final ✅
+        if self.is_oversubscribable() {
+            assert!(self.has_ended(now), "Fundraiser has not ended yet");
+        }
+    }
+
+    /// Checks that enough cosigners signed the transaction, `accounts` being
+    /// the trailing accounts of an admin instruction.
+    pub fn assert_cosigned(&self, accounts: &[AccountInfo]) {
+        let cosigners = &self.cosigners[..self.cosigner_count[0] as usize];
+
+        let signed = cosigners
+            .iter()
+            .filter(|cosigner| {
+                accounts
+                    .iter()
+                    .any(|account| account.key() == *cosigner && account.is_signer())
+            })
+            .count();
+
+        assert!(
+            signed >= self.threshold[0] as usize,
+            "Not enough cosigners signed"
+        );
+    }
+
+    /// Checks that `vault` is the token account recorded when the campaign
+    /// was created, or migrated to layout version 2.
+    pub fn assert_vault(&self, vault: &AccountInfo) {
+        assert_eq!(vault.key(), &self.vault, "You provided the wrong vault");
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused[0] == 1
+    }
+
+    pub fn has_attestor(&self) -> bool {
+        self.attestor != [0u8; 32]
+    }
+
+    /// project tokens owed for `amount` raised tokens, rounded down
+    pub fn allocation_for(&self, amount: u64) -> u64 {
+        let numerator = u64::from_le_bytes(self.allocation_numerator) as u128;
+        let denominator = u64::from_le_bytes(self.allocation_denominator) as u128;
+
+        (amount as u128 * numerator / denominator) as u64
+    }
+
+    /// Splits a contribution of an ended sale into `(excess, allocation)`.
+    ///
+    /// With `total = current_amount` and `target = amount_to_raise`, a contribution
+    /// `amount` is accepted for `amount * target / total` and the rest is excess.
+    /// Both figures are rounded down, each on its own:
+    /// - `excess = floor(amount * (total - target) / total)`, so the excess paid out
+    ///   across all contributors never exceeds the `total - target` the maker leaves
+    ///   in the vault
+    /// - `allocation = allocation_for(floor(amount * target / total))`, so the
+    ///   allocations never exceed what was deposited for `target`
+    ///
+    /// Any rounding dust stays in the vaults.
+    pub fn settlement_for(&self, amount: u64) -> (u64, u64) {
+        let total = u64::from_le_bytes(self.current_amount) as u128;
+        let target = u64::from_le_bytes(self.amount_to_raise) as u128;
+
+        if total <= target {
+            return (0, self.allocation_for(amount));
+        }
+
+        let excess = amount as u128 * (total - target) / total;
+        let accepted = amount as u128 * target / total;
+
+        (excess as u64, self.allocation_for(accepted as u64))
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         bytemuck::bytes_of(self).to_vec()
     }
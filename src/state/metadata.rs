@@ -0,0 +1,78 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::state::{AccountDiscriminator, ProgramAccount};
+
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_URI_LEN: usize = 128;
+
+/// Human readable details of a fundraiser, a PDA at `["metadata", fundraiser]`.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct CampaignMetadata {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub fundraiser: [u8; 32],
+    // utf-8, zero padded past `title_len`
+    pub title_len: [u8; 1],
+    pub title: [u8; MAX_TITLE_LEN],
+    // points at the off-chain description, zero padded past `uri_len`
+    pub uri_len: [u8; 1],
+    pub uri: [u8; MAX_URI_LEN],
+    // a `Category`
+    pub category: [u8; 1],
+    // sha256 of the campaign image
+    pub image_hash: [u8; 32],
+    pub bump: [u8; 1],
+}
+
+impl ProgramAccount for CampaignMetadata {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::CampaignMetadata;
+    const VERSION: u8 = 1;
+}
+
+impl CampaignMetadata {
+    pub const LEN: usize = core::mem::size_of::<CampaignMetadata>();
+
+    pub fn title(&self) -> &str {
+        core::str::from_utf8(&self.title[..self.title_len[0] as usize]).unwrap_or_default()
+    }
+
+    pub fn uri(&self) -> &str {
+        core::str::from_utf8(&self.uri[..self.uri_len[0] as usize]).unwrap_or_default()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Category {
+    Other = 0,
+    Charity = 1,
+    Education = 2,
+    Health = 3,
+    Environment = 4,
+    Technology = 5,
+    Arts = 6,
+    Community = 7,
+}
+
+impl TryFrom<&u8> for Category {
+    type Error = pinocchio::program_error::ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Category::Other),
+            1 => Ok(Category::Charity),
+            2 => Ok(Category::Education),
+            3 => Ok(Category::Health),
+            4 => Ok(Category::Environment),
+            5 => Ok(Category::Technology),
+            6 => Ok(Category::Arts),
+            7 => Ok(Category::Community),
+            _ => Err(pinocchio::program_error::ProgramError::InvalidInstructionData),
+        }
+    }
+}
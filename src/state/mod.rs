@@ -1,5 +1,15 @@
+pub mod account;
+pub mod config;
 pub mod contributor;
+pub mod dispute;
 pub mod fundraiser;
+pub mod metadata;
+pub mod registry;
 
+pub use account::*;
+pub use config::*;
 pub use contributor::*;
+pub use dispute::*;
 pub use fundraiser::*;
+pub use metadata::*;
+pub use registry::*;
@@ -0,0 +1,85 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::state::{AccountDiscriminator, ProgramAccount};
+
+pub const REGISTRY_PAGE_CAPACITY: usize = 32;
+
+/// Counts every campaign ever registered, a single PDA at `["registry"]`.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Registry {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub count: [u8; 8],
+    pub bump: [u8; 1],
+}
+
+impl ProgramAccount for Registry {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Registry;
+    const VERSION: u8 = 1;
+}
+
+impl Registry {
+    pub const LEN: usize = core::mem::size_of::<Registry>();
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
+
+/// Campaign number `index` lives in page `index / REGISTRY_PAGE_CAPACITY`
+/// at slot `index % REGISTRY_PAGE_CAPACITY`.
+pub fn registry_position(index: u64) -> (u64, usize) {
+    (
+        index / REGISTRY_PAGE_CAPACITY as u64,
+        (index % REGISTRY_PAGE_CAPACITY as u64) as usize,
+    )
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct RegistryEntry {
+    pub fundraiser: [u8; 32],
+    // the `Category` of its metadata
+    pub category: [u8; 1],
+    // 1 once `Delist` saw the campaign finished
+    pub closed: [u8; 1],
+}
+
+impl RegistryEntry {
+    pub fn is_closed(&self) -> bool {
+        self.closed[0] == 1
+    }
+}
+
+/// A page of campaigns in creation order, a PDA at `["registry", index]`.
+/// Entries are never moved, so paging stays stable as campaigns close.
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct RegistryPage {
+    // `AccountDiscriminator` and layout version, see `ProgramAccount`
+    pub discriminator: [u8; 1],
+    pub version: [u8; 1],
+    pub index: [u8; 8],
+    pub len: [u8; 1],
+    pub bump: [u8; 1],
+    pub entries: [RegistryEntry; REGISTRY_PAGE_CAPACITY],
+}
+
+impl ProgramAccount for RegistryPage {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::RegistryPage;
+    const VERSION: u8 = 1;
+}
+
+impl RegistryPage {
+    pub const LEN: usize = core::mem::size_of::<RegistryPage>();
+
+    pub fn entries(&self) -> &[RegistryEntry] {
+        &self.entries[..self.len[0] as usize]
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytemuck::bytes_of(self).to_vec()
+    }
+}
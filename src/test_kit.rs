@@ -0,0 +1,428 @@
+//! A litesvm harness for this program, behind the `test-kit` feature, shared
+//! by its own tests and by programs that CPI into it. Those also enable
+//! `no-entrypoint`, so only their own entrypoint is linked.
+//!
+//! [`FundraiserTestEnv`] derefs to the [`LiteSVM`] it drives, so anything the
+//! helpers don't cover can still be done on the svm directly.
+
+use std::ops::{Deref, DerefMut};
+
+use litesvm::{types::TransactionResult, LiteSVM};
+use litesvm_token::{
+    spl_token::{
+        self,
+        solana_program::{clock::Clock, program_pack::Pack},
+    },
+    CreateAssociatedTokenAccount, CreateMint, MintTo,
+};
+use solana_account::Account;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::{
+    client,
+    instructions::{ContributeArgs, InitializeFundraiser, UpdateMetadata},
+    state::{Contributor, Fundraiser, ProgramAccount, Registry},
+};
+
+/// where `cargo build-sbf` puts the program
+pub const PROGRAM_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/target/deploy/pinocchio_fundraising.so"
+);
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// tokens minted to every maker and funded contributor
+pub const STARTING_TOKENS: u64 = 1_000_000_000;
+
+/// A maker with its own mint, and the fundraiser and vault it would run.
+pub struct Campaign {
+    pub mint: Pubkey,
+    pub maker_ata: Pubkey,
+    pub vault: Pubkey,
+    pub ata_program: Pubkey,
+    pub token_program: Pubkey,
+    pub system_program: Pubkey,
+    pub fundraiser: (Pubkey, u8),
+    pub maker: Keypair,
+}
+
+pub struct FundraiserTestEnv {
+    pub svm: LiteSVM,
+}
+
+impl Deref for FundraiserTestEnv {
+    type Target = LiteSVM;
+
+    fn deref(&self) -> &LiteSVM {
+        &self.svm
+    }
+}
+
+impl DerefMut for FundraiserTestEnv {
+    fn deref_mut(&mut self) -> &mut LiteSVM {
+        &mut self.svm
+    }
+}
+
+impl Default for FundraiserTestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FundraiserTestEnv {
+    /// Loads the program built at [`PROGRAM_PATH`].
+    pub fn new() -> Self {
+        let program = std::fs::read(PROGRAM_PATH).unwrap_or_else(|err| {
+            panic!("reading {PROGRAM_PATH}, run `cargo build-sbf` first: {err}")
+        });
+
+        Self::with_program(&program)
+    }
+
+    /// Loads the program from `bytes`, such as an `include_bytes!` of a downstream build.
+    pub fn with_program(bytes: &[u8]) -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program(client::PROGRAM_ID, bytes);
+
+        Self { svm }
+    }
+
+    /// Writes the upgradeable loader's program data account for the program
+    /// with `authority` as its upgrade authority, which `InitializeConfig`
+    /// checks. `add_program` deploys without one.
+    pub fn set_upgrade_authority(&mut self, authority: &Pubkey) {
+        // `UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }`
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(authority.as_ref());
+
+        self.svm
+            .set_account(
+                client::program_data_address(),
+                Account {
+                    lamports: LAMPORTS_PER_SOL,
+                    data,
+                    owner: client::BPF_LOADER_UPGRADEABLE_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Sends `instructions` paid by `payer` against a fresh blockhash, so the
+    /// same transaction can be sent twice.
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        self.svm.expire_blockhash();
+        let signers = [&[payer][..], signers].concat();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers,
+            self.svm.latest_blockhash(),
+        );
+
+        self.svm.send_transaction(transaction)
+    }
+
+    /// A funded maker with its own mint, ready to create its fundraiser.
+    pub fn new_maker(&mut self) -> Campaign {
+        let maker = Keypair::new();
+        self.svm
+            .airdrop(&maker.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("Airdrop failed");
+
+        let mint = CreateMint::new(&mut self.svm, &maker)
+            .decimals(6)
+            .authority(&maker.pubkey())
+            .send()
+            .unwrap();
+        let maker_ata = CreateAssociatedTokenAccount::new(&mut self.svm, &maker, &mint)
+            .owner(&maker.pubkey())
+            .send()
+            .unwrap();
+
+        let fundraiser = Pubkey::find_program_address(
+            &[b"fundraiser".as_ref(), maker.pubkey().as_ref()],
+            &client::PROGRAM_ID,
+        );
+
+        Campaign {
+            mint,
+            maker_ata,
+            vault: client::associated_token_address(&fundraiser.0, &mint),
+            ata_program: client::ASSOCIATED_TOKEN_PROGRAM_ID,
+            token_program: spl_token::ID,
+            system_program: client::SYSTEM_PROGRAM_ID,
+            fundraiser,
+            maker,
+        }
+    }
+
+    /// A wallet holding [`STARTING_TOKENS`] of the campaign's mint.
+    pub fn fund_contributor(&mut self, campaign: &Campaign) -> Keypair {
+        let contributor = Keypair::new();
+        self.svm
+            .airdrop(&contributor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("Airdrop failed");
+
+        let contributor_ata =
+            CreateAssociatedTokenAccount::new(&mut self.svm, &contributor, &campaign.mint)
+                .owner(&contributor.pubkey())
+                .send()
+                .unwrap();
+        MintTo::new(
+            &mut self.svm,
+            &campaign.maker,
+            &campaign.mint,
+            &contributor_ata,
+            STARTING_TOKENS,
+        )
+        .send()
+        .unwrap();
+
+        contributor
+    }
+
+    /// the number of campaigns registered so far
+    pub fn registry_count(&self) -> u64 {
+        self.svm
+            .get_account(&client::registry_pda())
+            .map(|registry| u64::from_le_bytes(Registry::load(&registry.data).unwrap().count))
+            .unwrap_or(0)
+    }
+
+    /// Creates `campaign`'s fundraiser with `args` and `metadata`, after
+    /// minting the maker [`STARTING_TOKENS`] and creating the vault.
+    pub fn initialize(
+        &mut self,
+        campaign: &Campaign,
+        args: InitializeFundraiser,
+        metadata: UpdateMetadata,
+    ) -> TransactionResult {
+        let maker = campaign.maker.pubkey();
+
+        MintTo::new(
+            &mut self.svm,
+            &campaign.maker,
+            &campaign.mint,
+            &campaign.maker_ata,
+            STARTING_TOKENS,
+        )
+        .send()
+        .unwrap();
+
+        let instructions = [
+            client::create_associated_token_account(&maker, &campaign.fundraiser.0, &campaign.mint),
+            client::initialize(
+                &maker,
+                &campaign.mint,
+                self.registry_count(),
+                args,
+                metadata,
+            ),
+        ];
+        self.send(&instructions, &campaign.maker, &[])
+    }
+
+    /// A new maker's running campaign.
+    pub fn create_campaign(&mut self, args: InitializeFundraiser) -> Campaign {
+        let campaign = self.new_maker();
+
+        self.initialize(
+            &campaign,
+            args,
+            client::metadata("Test campaign", "", 0, [0; 32]).unwrap(),
+        )
+        .expect("Initialize failed");
+
+        campaign
+    }
+
+    /// A new campaign that one funded contributor per entry of `amounts`
+    /// contributed that amount to.
+    pub fn campaign_with_contributors(
+        &mut self,
+        args: InitializeFundraiser,
+        amounts: &[u64],
+    ) -> (Campaign, Vec<Keypair>) {
+        let campaign = self.create_campaign(args);
+
+        let contributors = amounts
+            .iter()
+            .map(|amount| {
+                let contributor = self.fund_contributor(&campaign);
+                self.contribute(&campaign, &contributor, *amount)
+                    .expect("Contribute failed");
+                contributor
+            })
+            .collect();
+
+        (campaign, contributors)
+    }
+
+    pub fn contribute(
+        &mut self,
+        campaign: &Campaign,
+        contributor: &Keypair,
+        amount: u64,
+    ) -> TransactionResult {
+        let instruction = client::contribute(
+            &contributor.pubkey(),
+            &campaign.maker.pubkey(),
+            &campaign.mint,
+            ContributeArgs { amount },
+        );
+
+        self.send(&[instruction], contributor, &[])
+    }
+
+    pub fn refund(&mut self, campaign: &Campaign, contributor: &Keypair) -> TransactionResult {
+        let instruction = client::refund(
+            &contributor.pubkey(),
+            &campaign.maker.pubkey(),
+            &campaign.mint,
+        );
+
+        self.send(&[instruction], contributor, &[])
+    }
+
+    /// `Claim` by the maker, still the campaign's authority unless handed off
+    pub fn claim(&mut self, campaign: &Campaign) -> TransactionResult {
+        let maker = campaign.maker.pubkey();
+        let instruction = client::claim(&maker, &maker, &campaign.mint);
+
+        self.send(&[instruction], &campaign.maker, &[])
+    }
+
+    /// `RequestClaim` by the maker, opening a timelocked campaign's challenge window
+    pub fn request_claim(&mut self, campaign: &Campaign) -> TransactionResult {
+        let maker = campaign.maker.pubkey();
+        let instruction = client::request_claim(&maker, &maker);
+
+        self.send(&[instruction], &campaign.maker, &[])
+    }
+
+    /// `ExecuteClaim` by the maker, once the timelock ran out
+    pub fn execute_claim(&mut self, campaign: &Campaign) -> TransactionResult {
+        let maker = campaign.maker.pubkey();
+        let instruction = client::execute_claim(&maker, &maker, &campaign.mint);
+
+        self.send(&[instruction], &campaign.maker, &[])
+    }
+
+    /// `Cancel` by the maker
+    pub fn cancel(&mut self, campaign: &Campaign) -> TransactionResult {
+        let maker = campaign.maker.pubkey();
+        let instruction = client::cancel(&maker, &maker);
+
+        self.send(&[instruction], &campaign.maker, &[])
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar::<Clock>(&clock);
+    }
+
+    pub fn warp_by(&mut self, seconds: i64) {
+        self.warp_to(self.now() + seconds);
+    }
+
+    /// Moves the clock `offset` seconds from the campaign's deadline,
+    /// `time_started + duration`, to probe either side of it.
+    pub fn warp_to_deadline(&mut self, campaign: &Campaign, offset: i64) {
+        let deadline = self.fundraiser(campaign).deadline();
+        self.warp_to(deadline + offset);
+    }
+
+    /// Moves the clock to the campaign's deadline, the first second it has ended.
+    pub fn warp_past_deadline(&mut self, campaign: &Campaign) {
+        self.warp_to_deadline(campaign, 0);
+    }
+
+    pub fn fundraiser(&self, campaign: &Campaign) -> Fundraiser {
+        let account = self.svm.get_account(&campaign.fundraiser.0).unwrap();
+        client::decode_fundraiser(&account.data).expect("not a fundraiser")
+    }
+
+    /// `wallet`'s ledger, `None` before it contributed or once it was refunded
+    pub fn ledger(&self, campaign: &Campaign, wallet: &Pubkey) -> Option<Contributor> {
+        let address = client::contributor_pda(&campaign.fundraiser.0, wallet);
+        let account = self.svm.get_account(&address)?;
+
+        client::decode_contributor(&account.data)
+    }
+
+    pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+        let token_account = self.svm.get_account(token_account).unwrap();
+        spl_token::state::Account::unpack(&token_account.data)
+            .unwrap()
+            .amount
+    }
+
+    /// Checks that the ledgers of `wallets`, every wallet that contributed,
+    /// add up to `current_amount` and that the vault holds exactly that until
+    /// it is claimed.
+    ///
+    /// After a split dispute the authority's share has already left the
+    /// vault, which then covers what the ledgers still get back, plus at most
+    /// a unit of rounding per wallet. Oversubscribed sales are left to their
+    /// own tests, as a claim only takes `amount_to_raise`.
+    pub fn assert_invariants(&self, campaign: &Campaign, wallets: &[Pubkey]) {
+        let fundraiser = self.fundraiser(campaign);
+        let current_amount = u64::from_le_bytes(fundraiser.current_amount);
+
+        let ledgers: Vec<u64> = wallets
+            .iter()
+            .filter_map(|wallet| self.ledger(campaign, wallet))
+            .map(|ledger| u64::from_le_bytes(ledger.amount))
+            .collect();
+        assert_eq!(
+            ledgers.iter().sum::<u64>(),
+            current_amount,
+            "contributor ledgers disagree with current_amount"
+        );
+
+        if fundraiser.is_oversubscribable() {
+            return;
+        }
+        let vault = self.token_balance(&campaign.vault);
+
+        if fundraiser.maker_share_bps != [0; 2] {
+            let owed: u64 = ledgers
+                .iter()
+                .map(|amount| fundraiser.refund_for(*amount))
+                .sum();
+            assert!(vault >= owed, "vault can't cover the refunds after a split");
+            assert!(
+                vault - owed <= wallets.len() as u64,
+                "vault holds more than rounding after a split"
+            );
+            return;
+        }
+
+        let expected = if fundraiser.is_claimed() {
+            0
+        } else {
+            current_amount
+        };
+        assert_eq!(vault, expected, "vault disagrees with current_amount");
+    }
+}
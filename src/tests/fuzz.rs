@@ -0,0 +1,191 @@
+//! Random sequences of initialize, contribute, refund, claim and cancel calls
+//! from many wallets, with clock warps in between, checking after every step
+//! that no vault, ledger or wallet balance goes out of line.
+//!
+//! Every sequence comes from a seed: a failure prints it, and
+//! `FUZZ_SEED=<seed> cargo test fuzz` replays just that sequence.
+//! `FUZZ_RUNS` sets how many seeds a normal run goes through.
+
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+use crate::{
+    client,
+    instructions::InitializeFundraiser,
+    test_kit::{Campaign, FundraiserTestEnv, STARTING_TOKENS},
+};
+
+const DEFAULT_RUNS: u64 = 8;
+const STEPS: usize = 40;
+const MAX_CAMPAIGNS: usize = 3;
+const WALLETS_PER_CAMPAIGN: usize = 4;
+
+/// splitmix64, enough to pick steps without pulling in a crate
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+}
+
+struct Wallet {
+    keypair: Keypair,
+    put_in: u64,
+    received: u64,
+}
+
+struct Run {
+    env: FundraiserTestEnv,
+    campaigns: Vec<(Campaign, Vec<Wallet>)>,
+}
+
+impl Run {
+    fn initialize(&mut self, rng: &mut Rng) {
+        let campaign = self.env.create_campaign(InitializeFundraiser {
+            amount_to_raise: rng.between(20_000_000, 100_000_000),
+            duration: rng.between(60, 3_600),
+            claim_timelock: 0,
+            arbiter: [0u8; 32],
+        });
+        let wallets = (0..WALLETS_PER_CAMPAIGN)
+            .map(|_| Wallet {
+                keypair: self.env.fund_contributor(&campaign),
+                put_in: 0,
+                received: 0,
+            })
+            .collect();
+
+        self.campaigns.push((campaign, wallets));
+    }
+
+    fn step(&mut self, rng: &mut Rng) {
+        if self.campaigns.is_empty() || (self.campaigns.len() < MAX_CAMPAIGNS && rng.below(10) == 0)
+        {
+            return self.initialize(rng);
+        }
+
+        let index = rng.below(self.campaigns.len() as u64) as usize;
+        let now = self.env.now();
+        let before = self.env.fundraiser(&self.campaigns[index].0);
+        let (campaign, wallets) = &mut self.campaigns[index];
+        let wallet = &mut wallets[rng.below(WALLETS_PER_CAMPAIGN as u64) as usize];
+        let wallet_ata = client::associated_token_address(&wallet.keypair.pubkey(), &campaign.mint);
+
+        match rng.below(8) {
+            0..=2 => {
+                // straddles the 10 token minimum
+                let amount = rng.between(5_000_000, 60_000_000);
+                if self
+                    .env
+                    .contribute(campaign, &wallet.keypair, amount)
+                    .is_ok()
+                {
+                    assert!(!before.has_ended(now), "contributed after the deadline");
+                    assert!(!before.is_claimed() && !before.is_cancelled());
+                    wallet.put_in += amount;
+                }
+            }
+            3 | 4 => {
+                let balance = self.env.token_balance(&wallet_ata);
+                if self.env.refund(campaign, &wallet.keypair).is_ok() {
+                    assert!(before.is_refundable(now), "refunded a live campaign");
+                    wallet.received += self.env.token_balance(&wallet_ata) - balance;
+                }
+            }
+            5 => {
+                if self.env.claim(campaign).is_ok() {
+                    assert!(before.target_reached(), "claimed short of the target");
+                    assert!(!before.is_claimed() && !before.is_cancelled());
+                }
+            }
+            6 => {
+                if self.env.cancel(campaign).is_ok() {
+                    assert!(!before.has_ended(now), "cancelled after the deadline");
+                }
+            }
+            _ => {
+                // half the warps land right around a deadline, time never
+                // runs backwards though
+                if rng.below(2) == 0 {
+                    let offset = rng.between(0, 2) as i64 - 1;
+                    self.env.warp_to((before.deadline() + offset).max(now + 1));
+                } else {
+                    self.env.warp_by(rng.between(1, 1_200) as i64);
+                }
+            }
+        }
+    }
+
+    fn assert_invariants(&self) {
+        for (campaign, wallets) in &self.campaigns {
+            let keys: Vec<Pubkey> = wallets.iter().map(|w| w.keypair.pubkey()).collect();
+            self.env.assert_invariants(campaign, &keys);
+
+            for wallet in wallets {
+                assert!(
+                    wallet.received <= wallet.put_in,
+                    "a wallet got back more than it put in"
+                );
+
+                // what a wallet holds plus what its ledger still owes it is
+                // everything it started with
+                let ata =
+                    client::associated_token_address(&wallet.keypair.pubkey(), &campaign.mint);
+                let owed = self
+                    .env
+                    .ledger(campaign, &wallet.keypair.pubkey())
+                    .map_or(0, |ledger| u64::from_le_bytes(ledger.amount));
+                assert_eq!(self.env.token_balance(&ata) + owed, STARTING_TOKENS);
+            }
+        }
+    }
+}
+
+fn run(seed: u64) {
+    let mut rng = Rng(seed);
+    let mut run = Run {
+        env: FundraiserTestEnv::new(),
+        campaigns: Vec::new(),
+    };
+
+    for _ in 0..STEPS {
+        run.step(&mut rng);
+        run.assert_invariants();
+    }
+}
+
+fn env_u64(name: &str) -> Option<u64> {
+    std::env::var(name).ok().map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{name} should be a number"))
+    })
+}
+
+#[test]
+fn fuzz_contribute_refund_claim_sequences() {
+    let seeds = match env_u64("FUZZ_SEED") {
+        Some(seed) => seed..seed + 1,
+        None => 0..env_u64("FUZZ_RUNS").unwrap_or(DEFAULT_RUNS),
+    };
+
+    for seed in seeds {
+        if std::panic::catch_unwind(|| run(seed)).is_err() {
+            panic!("sequence failed, replay it with FUZZ_SEED={seed}");
+        }
+    }
+}
@@ -1,16 +1,14 @@
 use crate::state::Fundraiser;
 
 #[cfg(test)]
-mod tests {
+mod fuzz;
 
-    use std::{io::Error, path::PathBuf};
+#[cfg(test)]
+mod tests {
 
-    use litesvm::LiteSVM;
+    use litesvm::types::{FailedTransactionMetadata, TransactionMetadata, TransactionResult};
     use litesvm_token::{
-        spl_token::{
-            self,
-            solana_program::{msg, rent::Rent, sysvar::SysvarId},
-        },
+        spl_token::{self, solana_program::clock::Clock},
         CreateAssociatedTokenAccount, CreateMint, MintTo,
     };
 
@@ -21,225 +19,397 @@ mod tests {
     use solana_pubkey::Pubkey;
     use solana_signer::Signer;
     use solana_transaction::Transaction;
-    use spl_associated_token_account::solana_program::program_pack::Pack;
 
-    use crate::instructions::InitializeFundraiser;
+    use crate::attestation::{ed25519_instruction_data, Voucher, ED25519_PROGRAM_ID};
+    use crate::client;
+    use crate::instructions::{
+        AllowlistArgs, ContributeArgs, Extend, FundraisingInstructions, InitializeFundraiser,
+        InitializeSale, OpenDispute, ProposeAuthority, ResolveDispute, SetAllowlist, SetAttestor,
+        SetCosigners, SetPause, UpdateMetadata,
+    };
+    use crate::merkle::MerkleTree;
+    use crate::test_kit::{Campaign, FundraiserTestEnv};
 
     const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID); //"CntDHuHyUa1sEyLEYoHbrYdzM2G4VeDHSdQjQXXdRh6E";
     const TOKEN_PROGRAM_ID: Pubkey = spl_token::ID;
-    const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
 
     fn program_id() -> Pubkey {
         PROGRAM_ID
     }
 
-    fn setup() -> (LiteSVM, ReusableState) {
-        let mut svm = LiteSVM::new();
-        let payer = Keypair::new();
+    fn setup() -> (FundraiserTestEnv, Campaign) {
+        let mut svm = FundraiserTestEnv::new();
+        let state = svm.new_maker();
+        (svm, state)
+    }
 
-        svm.airdrop(&payer.pubkey(), 10 * LAMPORTS_PER_SOL)
-            .expect("Airdrop failed");
+    /// the events `instructions`, sent by `payer` as `tx`, emitted as inner instructions
+    fn cpi_events(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        tx: &TransactionMetadata,
+    ) -> Vec<crate::events::FundraiserEvent> {
+        let keys = Message::new(instructions, Some(payer)).account_keys;
 
-        // Load program SO file
-        msg!("The path is!! {}", env!("CARGO_MANIFEST_DIR"));
+        tx.inner_instructions
+            .iter()
+            .flatten()
+            .filter_map(|inner| {
+                let program_id = keys[inner.instruction.program_id_index as usize];
+                crate::events::FundraiserEvent::from_instruction_data(
+                    &program_id.to_bytes(),
+                    &inner.instruction.data,
+                )
+            })
+            .collect()
+    }
 
-        let bytes = include_bytes!("../../target/deploy/pinocchio_fundraising.so");
-        svm.add_program(program_id(), bytes);
+    pub fn create_fundraiser(svm: &mut FundraiserTestEnv, state: &Campaign) -> TransactionResult {
+        let amount_to_receive: u64 = 100_000_000; // 100 tokens with 6 decimal places
+        let amount_to_give: u64 = 400_000_000; // 500 tokens with 6 decimal places
 
-        let mint = CreateMint::new(&mut svm, &payer)
-            .decimals(6)
-            .authority(&payer.pubkey())
-            .send()
-            .unwrap();
-        msg!("Mint A: {}", mint);
+        let init_data_ix: InitializeFundraiser = InitializeFundraiser {
+            amount_to_raise: amount_to_give,
+            duration: amount_to_receive,
+            claim_timelock: 0,
+            arbiter: [0u8; 32],
+        };
 
-        // Create the maker's associated token account for Mint A
-        let maker_ata = CreateAssociatedTokenAccount::new(&mut svm, &payer, &mint)
-            .owner(&payer.pubkey())
-            .send()
-            .unwrap();
-        msg!("Maker ATA A: {}\n", maker_ata);
+        create_fundraiser_with(svm, state, init_data_ix)
+    }
 
-        // Derive the PDA for the escrow account using the maker's public key and a seed value
-        let fundraiser = Pubkey::find_program_address(
-            &[b"fundraiser".as_ref(), payer.pubkey().as_ref()],
-            &PROGRAM_ID,
-        );
-        msg!("Fundraiser PDA: {}\n", fundraiser.0);
+    pub fn create_fundraiser_with(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        init_data_ix: InitializeFundraiser,
+    ) -> TransactionResult {
+        svm.initialize(state, init_data_ix, metadata("Clean water", 1))
+    }
+
+    pub fn contribute(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+    ) -> Result<Keypair, FailedTransactionMetadata> {
+        let contributor = svm.fund_contributor(state);
+        svm.contribute(state, &contributor, 10_000_000)?;
+
+        Ok(contributor)
+    }
 
-        // Derive the PDA for the vault associated token account using the escrow PDA and Mint A
-        let vault = spl_associated_token_account::get_associated_token_address(
-            &fundraiser.0, // owner will be the escrow PDA
-            &mint,         // mint
+    /// sends a `Contribute` with raw `args`, to probe how they are decoded
+    pub fn send_contribute(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        contributor: &Keypair,
+        args: Vec<u8>,
+    ) -> TransactionResult {
+        let mut instruction = client::contribute(
+            &contributor.pubkey(),
+            &state.maker.pubkey(),
+            &state.mint,
+            ContributeArgs { amount: 0 },
         );
+        // keep the discriminator, swap the arguments
+        instruction.data.truncate(1);
+        instruction.data.extend(args);
 
-        msg!("Vault PDA: {}\n", vault);
+        svm.send(&[instruction], contributor, &[])
+    }
 
-        // Define program IDs for associated token program, token program, and system program
-        let asspciated_token_program = ASSOCIATED_TOKEN_PROGRAM_ID.parse::<Pubkey>().unwrap();
-        let token_program = TOKEN_PROGRAM_ID;
-        let system_program = solana_sdk_ids::system_program::ID;
+    pub fn set_allowlist(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        root: [u8; 32],
+    ) -> TransactionResult {
+        let maker = state.maker.pubkey();
+        let instruction = client::set_allowlist(&maker, &maker, SetAllowlist { root });
 
-        let reusable_state = ReusableState {
-            mint,
-            ata_program: asspciated_token_program,
-            token_program,
-            system_program,
-            vault,
-            maker: payer,
-            maker_ata,
-            fundraiser,
-        };
-        (svm, reusable_state)
+        svm.send(&[instruction], &state.maker, &[])
+    }
+
+    pub struct SaleState {
+        pub allocation_mint: Pubkey,
+        pub allocation_vault: Pubkey,
+        pub maker_allocation_ata: Pubkey,
+    }
+
+    fn load_registry_page(svm: &FundraiserTestEnv, page_index: u64) -> crate::state::RegistryPage {
+        let page = svm
+            .get_account(&client::registry_page_pda(page_index))
+            .unwrap();
+        *bytemuck::from_bytes::<crate::state::RegistryPage>(&page.data)
     }
 
-    pub struct ReusableState {
-        pub mint: Pubkey,
-        pub maker_ata: Pubkey,
-        pub vault: Pubkey,
-        pub ata_program: Pubkey,
-        pub token_program: Pubkey,
-        pub system_program: Pubkey,
-        pub fundraiser: (Pubkey, u8),
-        pub maker: Keypair,
+    /// `Delist` against the page holding campaign number `registry_index`
+    pub fn delist(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        registry_index: u64,
+    ) -> TransactionResult {
+        let instruction = client::delist(&state.maker.pubkey(), registry_index);
+
+        svm.send(&[instruction], &state.maker, &[])
+    }
+
+    pub fn metadata(title: &str, category: u8) -> UpdateMetadata {
+        client::metadata(
+            title,
+            "https://example.com/campaign.json",
+            category,
+            crate::merkle::hashv(&[title.as_bytes()]),
+        )
+        .unwrap()
+    }
+
+    pub fn update_metadata(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        metadata: UpdateMetadata,
+    ) -> TransactionResult {
+        let maker = state.maker.pubkey();
+        let instruction = client::update_metadata(&maker, &maker, metadata);
+
+        svm.send(&[instruction], &state.maker, &[])
     }
 
-    pub fn create_fundraiser(svm: &mut LiteSVM, state: &ReusableState) -> Result<(), Error> {
-        let mint = state.mint;
+    pub fn create_sale(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        amount_to_raise: u64,
+        duration: u64,
+        allow_oversubscription: bool,
+    ) -> Result<SaleState, FailedTransactionMetadata> {
         let payer = &state.maker;
-        let maker_ata = state.maker_ata;
-        let vault = state.vault;
-        let system_program = state.system_program;
-        let token_program = state.token_program;
-        let ata_program = state.ata_program;
         let fundraiser = state.fundraiser;
 
-        MintTo::new(svm, &payer, &mint, &maker_ata, 1_000_000_000)
+        MintTo::new(svm, payer, &state.mint, &state.maker_ata, 1_000_000_000)
             .send()
             .unwrap();
-        litesvm_token::CreateAssociatedTokenAccount::new(svm, payer, &mint)
+        litesvm_token::CreateAssociatedTokenAccount::new(svm, payer, &state.mint)
             .owner(&fundraiser.0)
             .token_program_id(&TOKEN_PROGRAM_ID)
             .send()
             .unwrap();
 
-        let amount_to_receive: u64 = 100_000_000; // 100 tokens with 6 decimal places
-        let amount_to_give: u64 = 400_000_000; // 500 tokens with 6 decimal places
+        // the project token the sale hands out
+        let allocation_mint = CreateMint::new(svm, payer)
+            .decimals(6)
+            .authority(&payer.pubkey())
+            .send()
+            .unwrap();
+        let maker_allocation_ata = CreateAssociatedTokenAccount::new(svm, payer, &allocation_mint)
+            .owner(&payer.pubkey())
+            .send()
+            .unwrap();
+        MintTo::new(
+            svm,
+            payer,
+            &allocation_mint,
+            &maker_allocation_ata,
+            10_000_000_000,
+        )
+        .send()
+        .unwrap();
+        let allocation_vault = CreateAssociatedTokenAccount::new(svm, payer, &allocation_mint)
+            .owner(&fundraiser.0)
+            .send()
+            .unwrap();
 
-        let init_data_ix: InitializeFundraiser = InitializeFundraiser {
-            amount_to_raise: amount_to_give,
-            duration: amount_to_receive,
-        };
+        let sale_ix = client::initialize_sale(
+            &payer.pubkey(),
+            &state.mint,
+            &allocation_mint,
+            svm.registry_count(),
+            InitializeSale {
+                fundraiser: InitializeFundraiser {
+                    amount_to_raise,
+                    duration,
+                    claim_timelock: 0,
+                    arbiter: [0u8; 32],
+                },
+                allocation_numerator: 5,   // 5 project tokens ...
+                allocation_denominator: 2, // ... for every 2 raised tokens
+                allow_oversubscription: allow_oversubscription as u64,
+            },
+            metadata("Project token sale", 5),
+        );
+        svm.send(&[sale_ix], payer, &[])?;
+
+        Ok(SaleState {
+            allocation_mint,
+            allocation_vault,
+            maker_allocation_ata,
+        })
+    }
 
-        let init_data_ser = init_data_ix.to_bytes();
+    /// `ClaimAllocation` into a new allocation token account of the contributor
+    pub fn claim_allocation(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        sale: &SaleState,
+        contributor: &Keypair,
+    ) -> Result<Pubkey, FailedTransactionMetadata> {
+        let contributor_allocation_ata =
+            CreateAssociatedTokenAccount::new(svm, contributor, &sale.allocation_mint)
+                .owner(&contributor.pubkey())
+                .send()
+                .unwrap();
 
-        let make_data = [
-            vec![crate::instructions::FundraisingInstructions::Initialize as u8],
-            init_data_ser, // Discriminator for "Make" instruction
-        ]
-        .concat();
+        let instruction = client::claim_allocation(
+            &contributor.pubkey(),
+            &state.maker.pubkey(),
+            &sale.allocation_mint,
+        );
+        svm.send(&[instruction], contributor, &[])?;
 
-        msg!("here's the fundraiser from client: {}", fundraiser.0);
+        Ok(contributor_allocation_ata)
+    }
 
-        let init_ix = Instruction {
-            program_id: program_id(),
-            accounts: vec![
-                AccountMeta::new(payer.pubkey(), true),
-                AccountMeta::new(mint, false),
-                AccountMeta::new(fundraiser.0, false),
-                AccountMeta::new(vault, false),
-                AccountMeta::new(system_program, false),
-                AccountMeta::new(token_program, false),
-                AccountMeta::new(ata_program, false),
-                AccountMeta::new(Rent::id(), false),
-            ],
-            data: make_data,
-        };
+    /// `Settle` into a new allocation token account of the contributor
+    pub fn settle(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        sale: &SaleState,
+        contributor: &Keypair,
+    ) -> Result<Pubkey, FailedTransactionMetadata> {
+        let contributor_allocation_ata =
+            CreateAssociatedTokenAccount::new(svm, contributor, &sale.allocation_mint)
+                .owner(&contributor.pubkey())
+                .send()
+                .unwrap();
 
-        let message = Message::new(&[init_ix], Some(&payer.pubkey()));
-        let recent_blockhash = svm.latest_blockhash();
+        let instruction = client::settle(
+            &contributor.pubkey(),
+            &state.maker.pubkey(),
+            &state.mint,
+            &sale.allocation_mint,
+        );
+        svm.send(&[instruction], contributor, &[])?;
 
-        let transaction = Transaction::new(&[&payer], message, recent_blockhash);
+        Ok(contributor_allocation_ata)
+    }
 
-        // Send the transaction and capture the result
-        let tx = svm.send_transaction(transaction).unwrap();
-        msg!("tx logs: {:#?}", tx.logs);
-        msg!("\n\nMake transaction sucessfull");
-        msg!("CUs Consumed: {}", tx.compute_units_consumed);
+    pub fn initialize_config(svm: &mut FundraiserTestEnv, guardian: &Keypair) -> TransactionResult {
+        let instruction = client::initialize_config(&guardian.pubkey());
 
-        Ok(())
+        svm.send(&[instruction], guardian, &[])
     }
 
-    pub fn contribute(svm: &mut LiteSVM, state: &ReusableState) -> Result<(), Error> {
-        let mint = state.mint;
-        let payer = &state.maker;
-        // let maker_ata = state.maker_ata;
-        let vault = state.vault;
-        let system_program = state.system_program;
-        let token_program = state.token_program;
-        let ata_program = state.ata_program;
-        let fundraiser = state.fundraiser;
+    pub fn set_pause(
+        svm: &mut FundraiserTestEnv,
+        guardian: &Keypair,
+        fundraiser: Option<Pubkey>,
+        paused: bool,
+        reason: u8,
+    ) -> TransactionResult {
+        let instruction = client::set_pause(
+            &guardian.pubkey(),
+            fundraiser.as_ref(),
+            SetPause {
+                paused: paused as u8,
+                reason,
+            },
+        );
 
-        let contributor = Keypair::new();
+        svm.send(&[instruction], guardian, &[])
+    }
 
-        svm.airdrop(&contributor.pubkey(), 10 * LAMPORTS_PER_SOL)
-            .expect("Airdrop failed");
+    /// sends `instruction` with `cosigners` appended as signers, see `client::with_cosigners`
+    pub fn send_cosigned(
+        svm: &mut FundraiserTestEnv,
+        instruction: Instruction,
+        authority: &Keypair,
+        cosigners: &[&Keypair],
+    ) -> TransactionResult {
+        let cosigner_keys: Vec<Pubkey> =
+            cosigners.iter().map(|cosigner| cosigner.pubkey()).collect();
 
-        let contributor_ata = CreateAssociatedTokenAccount::new(svm, &contributor, &mint)
-            .owner(&contributor.pubkey())
-            .send()
-            .unwrap();
-        msg!("Contributor ATA A: {}\n", &contributor_ata);
+        svm.send(
+            &[client::with_cosigners(instruction, &cosigner_keys)],
+            authority,
+            cosigners,
+        )
+    }
 
-        // pinocchio_token::state::TokenAccount::(&contributor_ata)?;
+    pub fn object_claim(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        objector: &Keypair,
+    ) -> TransactionResult {
+        let instruction = client::object_claim(&objector.pubkey(), &state.maker.pubkey());
 
-        MintTo::new(svm, &payer, &mint, &contributor_ata, 1_000_000_000)
-            .send()
-            .unwrap();
+        svm.send(&[instruction], objector, &[])
+    }
 
-        let contributor_pda = Pubkey::find_program_address(
-            &[b"contributor".as_ref(), contributor.pubkey().as_ref()],
-            &PROGRAM_ID,
+    pub fn open_dispute(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        opener: &Keypair,
+        reason: u8,
+    ) -> TransactionResult {
+        let instruction = client::open_dispute(
+            &opener.pubkey(),
+            &state.maker.pubkey(),
+            OpenDispute { reason },
         );
-        msg!("Fundraiser PDA: {}\n", contributor_pda.0);
 
-        let contribute_ix_data = [
-            vec![crate::instructions::FundraisingInstructions::Contribute as u8],
-            10_000_000u64.to_le_bytes().to_vec(), // Discriminator for "Make" instruction
-        ]
-        .concat();
+        svm.send(&[instruction], opener, &[])
+    }
 
-        let contribute_ix = Instruction {
-            program_id: program_id(),
-            accounts: vec![
-                AccountMeta::new(contributor.pubkey(), true),
-                AccountMeta::new(mint, false),
-                AccountMeta::new(fundraiser.0, false),
-                AccountMeta::new(vault, false),
-                AccountMeta::new(contributor_ata, false),
-                AccountMeta::new(contributor_pda.0, false),
-                AccountMeta::new(system_program, false),
-                AccountMeta::new(token_program, false),
-                AccountMeta::new(ata_program, false),
-                AccountMeta::new(Rent::id(), false),
-            ],
-            data: contribute_ix_data,
-        };
+    /// `ResolveDispute` paying out to the maker, who pays the fee for the arbiter
+    pub fn resolve_dispute(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        arbiter: &Keypair,
+        resolution: ResolveDispute,
+    ) -> TransactionResult {
+        let maker = state.maker.pubkey();
+        let instruction =
+            client::resolve_dispute(&arbiter.pubkey(), &maker, &state.mint, &maker, resolution);
 
-        let message = Message::new(&[contribute_ix], Some(&contributor.pubkey()));
-        let recent_blockhash = svm.latest_blockhash();
+        svm.send(&[instruction], &state.maker, &[arbiter])
+    }
+
+    pub fn set_attestor(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        attestor: [u8; 32],
+    ) -> TransactionResult {
+        let maker = state.maker.pubkey();
+        let instruction = client::set_attestor(&maker, &maker, SetAttestor { attestor });
+
+        svm.send(&[instruction], &state.maker, &[])
+    }
 
-        let transaction = Transaction::new(&[&contributor], message, recent_blockhash);
+    pub fn send_attested_contribute(
+        svm: &mut FundraiserTestEnv,
+        state: &Campaign,
+        contributor: &Keypair,
+        attestor: &Keypair,
+        voucher: &Voucher,
+        amount: u64,
+    ) -> TransactionResult {
+        let signature = attestor.sign_message(&voucher.to_bytes());
+        let ed25519_ix = Instruction {
+            program_id: Pubkey::new_from_array(ED25519_PROGRAM_ID),
+            accounts: vec![],
+            data: ed25519_instruction_data(
+                &attestor.pubkey().to_bytes(),
+                &<[u8; 64]>::try_from(signature.as_ref()).unwrap(),
+                voucher,
+            ),
+        };
 
-        // Send the transaction and capture the result
-        let tx = svm.send_transaction(transaction).unwrap();
-        msg!("tx logs: {:#?}", tx.logs);
-        msg!("\n\nMake transaction sucessfull");
-        msg!("CUs Consumed: {}", tx.compute_units_consumed);
+        let contribute_ix = client::with_voucher(client::contribute(
+            &contributor.pubkey(),
+            &state.maker.pubkey(),
+            &state.mint,
+            ContributeArgs { amount },
+        ));
 
-        // [contributor, mint, fundraiser, vault, contributor_ata, contributor_pda, system_program, token_program, associated_token_program, rent_sysvar @ ..]
-        Ok(())
+        svm.send(&[ed25519_ix, contribute_ix], contributor, &[])
     }
+
     #[test]
     pub fn test_init_instruction() {
         let (mut svm, state) = setup();
@@ -249,15 +419,13 @@ mod tests {
         assert_eq!(program_id, PROGRAM_ID);
         create_fundraiser(&mut svm, &state).unwrap();
 
-        let fundraiser_state = svm.get_account(&state.fundraiser.0).unwrap();
-
-        let maker_deserialized_ata =
-            bytemuck::try_from_bytes::<crate::state::Fundraiser>(&fundraiser_state.data).unwrap();
-        // spl_token::state::Account::unpack(fundraiser_state.data.as_slice()).unwrap();
-        msg!(
-            "new user token bump: {:#?}",
-            maker_deserialized_ata.amount_to_raise
+        let fundraiser_state = svm.fundraiser(&state);
+        assert_eq!(fundraiser_state.maker, state.maker.pubkey().to_bytes());
+        assert_eq!(
+            fundraiser_state.amount_to_raise,
+            400_000_000u64.to_le_bytes()
         );
+        assert_eq!(fundraiser_state.vault, state.vault.to_bytes());
     }
 
     #[test]
@@ -272,19 +440,2268 @@ mod tests {
         contribute(&mut svm, &state).unwrap(); // user 2 contributes
         contribute(&mut svm, &state).unwrap(); // user 3 contributes
 
+        let fundraiser_state = svm.fundraiser(&state);
+        assert_eq!(fundraiser_state.current_amount, 30_000_000u64.to_le_bytes());
+        assert_eq!(svm.token_balance(&state.vault), 30_000_000);
+    }
+
+    #[test]
+    pub fn test_sale_claim_allocation() {
+        let (mut svm, state) = setup();
+
+        let sale = create_sale(&mut svm, &state, 30_000_000, 3_600, false).unwrap();
+
+        // the maker deposited the allocation for the whole target
+        let allocation_vault = svm.get_account(&sale.allocation_vault).unwrap();
+        let allocation_vault =
+            litesvm_token::spl_token::state::Account::unpack(&allocation_vault.data).unwrap();
+        assert_eq!(allocation_vault.amount, 75_000_000);
+
+        let contributors = [
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+        ];
+
+        svm.warp_past_deadline(&state);
+
+        for contributor in contributors.iter() {
+            let contributor_allocation_ata =
+                claim_allocation(&mut svm, &state, &sale, contributor).unwrap();
+            let contributor_allocation_ata = svm.get_account(&contributor_allocation_ata).unwrap();
+            let contributor_allocation_ata =
+                litesvm_token::spl_token::state::Account::unpack(&contributor_allocation_ata.data)
+                    .unwrap();
+
+            // 10 raised tokens buy 25 project tokens
+            assert_eq!(contributor_allocation_ata.amount, 25_000_000);
+        }
+
+        let allocation_vault = svm.get_account(&sale.allocation_vault).unwrap();
+        let allocation_vault =
+            litesvm_token::spl_token::state::Account::unpack(&allocation_vault.data).unwrap();
+        assert_eq!(allocation_vault.amount, 0);
+        // the maker kept what the target didn't need
+        assert_eq!(
+            svm.token_balance(&sale.maker_allocation_ata),
+            10_000_000_000 - 75_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_oversubscribed_sale_settle() {
+        let (mut svm, state) = setup();
+
+        // 30 tokens come in against a 20 token target
+        let sale = create_sale(&mut svm, &state, 20_000_000, 3_600, true).unwrap();
+
+        let contributors = [
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+        ];
+        assert_eq!(svm.token_balance(&state.vault), 30_000_000);
+
+        svm.warp_past_deadline(&state);
+
+        for contributor in contributors.iter() {
+            let contributor_ata = spl_associated_token_account::get_associated_token_address(
+                &contributor.pubkey(),
+                &state.mint,
+            );
+            let before = svm.token_balance(&contributor_ata);
+
+            let contributor_allocation_ata = settle(&mut svm, &state, &sale, contributor).unwrap();
+
+            // a third of each 10 token contribution is excess, rounded down
+            assert_eq!(svm.token_balance(&contributor_ata) - before, 3_333_333);
+            // the accepted 6.666666 tokens buy 16.666665 project tokens
+            assert_eq!(svm.token_balance(&contributor_allocation_ata), 16_666_665);
+        }
+
+        svm.claim(&state).unwrap();
+
+        // rounding dust stays behind, the vaults never end up short
+        assert_eq!(svm.token_balance(&state.vault), 1);
+        assert_eq!(svm.token_balance(&sale.allocation_vault), 5);
+    }
+
+    #[test]
+    pub fn test_allowlisted_contribute() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let invited = svm.fund_contributor(&state);
+        let stranger = svm.fund_contributor(&state);
+
+        let allowlist = [
+            (Keypair::new().pubkey().to_bytes(), 50_000_000),
+            (invited.pubkey().to_bytes(), 15_000_000),
+            (Keypair::new().pubkey().to_bytes(), 50_000_000),
+        ];
+        let tree = MerkleTree::new(&allowlist);
+        set_allowlist(&mut svm, &state, tree.root()).unwrap();
+
+        let args = |amount: u64, cap: u64, proof: &[[u8; 32]]| {
+            [
+                ContributeArgs { amount }.to_bytes(),
+                AllowlistArgs { cap }.to_bytes(),
+                proof.concat(),
+            ]
+            .concat()
+        };
+        let proof = tree.proof(1);
+
+        send_contribute(
+            &mut svm,
+            &state,
+            &invited,
+            args(10_000_000, 15_000_000, &proof),
+        )
+        .unwrap();
+
+        // the leaf caps the running total, not each contribution
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &invited,
+            args(10_000_000, 15_000_000, &proof)
+        )
+        .is_err());
+        // claiming a bigger cap breaks the proof
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &invited,
+            args(10_000_000, 50_000_000, &proof)
+        )
+        .is_err());
+        // someone else's proof doesn't help a stranger
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &stranger,
+            args(10_000_000, 15_000_000, &proof)
+        )
+        .is_err());
+
+        // and the root is frozen now that contributions started
+        assert!(set_allowlist(&mut svm, &state, [0u8; 32]).is_err());
+        assert_eq!(svm.token_balance(&state.vault), 10_000_000);
+    }
+
+    #[test]
+    pub fn test_merkle_proofs() {
+        let allowlist = (0..5u64)
+            .map(|cap| (Keypair::new().pubkey().to_bytes(), cap * 1_000_000))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(&allowlist);
+
+        for (index, (wallet, cap)) in allowlist.iter().enumerate() {
+            let leaf = crate::merkle::leaf(wallet, *cap);
+            assert!(crate::merkle::verify(
+                &tree.proof(index),
+                &tree.root(),
+                leaf
+            ));
+            assert!(!crate::merkle::verify(
+                &tree.proof(index),
+                &tree.root(),
+                crate::merkle::leaf(wallet, cap + 1)
+            ));
+        }
+    }
+
+    #[test]
+    pub fn test_attested_contribute() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let attestor = Keypair::new();
+        set_attestor(&mut svm, &state, attestor.pubkey().to_bytes()).unwrap();
+
+        let contributor = svm.fund_contributor(&state);
+        let now = svm.get_sysvar::<Clock>().unix_timestamp;
+        let voucher = Voucher {
+            fundraiser: state.fundraiser.0.to_bytes(),
+            wallet: contributor.pubkey().to_bytes(),
+            max_amount: 20_000_000,
+            expires_at: now + 600,
+            nonce: 1,
+        };
+
+        // no voucher, no contribution
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            ContributeArgs { amount: 10_000_000 }.to_bytes()
+        )
+        .is_err());
+
+        send_attested_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            &attestor,
+            &voucher,
+            10_000_000,
+        )
+        .unwrap();
+
+        // the same voucher can't be replayed
+        assert!(send_attested_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            &attestor,
+            &voucher,
+            10_000_000
+        )
+        .is_err());
+
+        // vouchers signed by anyone else are rejected
+        let forged = Voucher {
+            nonce: 2,
+            ..voucher
+        };
+        assert!(send_attested_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            &Keypair::new(),
+            &forged,
+            10_000_000
+        )
+        .is_err());
+
+        // nor can a voucher be stretched past its amount
+        assert!(send_attested_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            &attestor,
+            &forged,
+            30_000_000
+        )
+        .is_err());
+
+        send_attested_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            &attestor,
+            &forged,
+            10_000_000,
+        )
+        .unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 20_000_000);
+    }
+
+    #[test]
+    pub fn test_guardian_pause() {
+        use crate::events::{FundraiserEvent, Paused, Unpaused};
+
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        let guardian = Keypair::new();
+        svm.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        svm.set_upgrade_authority(&guardian.pubkey());
+
+        // only the upgrade authority can set up the config and pick the guardian
+        assert!(initialize_config(&mut svm, &state.maker).is_err());
+        initialize_config(&mut svm, &guardian).unwrap();
+
+        // only the guardian can pause
+        assert!(set_pause(&mut svm, &state.maker, Some(state.fundraiser.0), true, 1).is_err());
+
+        // pausing a campaign stops contributions to it
+        let tx = set_pause(&mut svm, &guardian, Some(state.fundraiser.0), true, 1).unwrap();
+        let now = svm.now().to_le_bytes();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Paused(Paused {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                guardian: guardian.pubkey().to_bytes(),
+                reason: 1,
+                paused_at: now,
+            })]
+        );
         let fundraiser_state = svm.get_account(&state.fundraiser.0).unwrap();
+        let fundraiser_state =
+            bytemuck::try_from_bytes::<crate::state::Fundraiser>(&fundraiser_state.data).unwrap();
+        assert!(fundraiser_state.is_paused());
+        assert_eq!(fundraiser_state.pause_reason, [1]);
+        let latecomer = svm.fund_contributor(&state);
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &latecomer,
+            ContributeArgs { amount: 10_000_000 }.to_bytes()
+        )
+        .is_err());
+
+        let tx = set_pause(&mut svm, &guardian, Some(state.fundraiser.0), false, 2).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Unpaused(Unpaused {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                guardian: guardian.pubkey().to_bytes(),
+                reason: 2,
+                unpaused_at: now,
+            })]
+        );
+        send_contribute(
+            &mut svm,
+            &state,
+            &latecomer,
+            ContributeArgs { amount: 10_000_000 }.to_bytes(),
+        )
+        .unwrap();
+
+        // pausing the program stops every campaign
+        let tx = set_pause(&mut svm, &guardian, None, true, 3).unwrap();
+        assert!(matches!(
+            FundraiserEvent::from_logs(&tx.logs)[..],
+            [FundraiserEvent::Paused(Paused {
+                fundraiser: [0; 32],
+                reason: 3,
+                ..
+            })]
+        ));
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &latecomer,
+            ContributeArgs { amount: 10_000_000 }.to_bytes()
+        )
+        .is_err());
+
+        // but refunds keep flowing so nothing is trapped
+        svm.warp_past_deadline(&state);
+        svm.refund(&state, &contributor).unwrap();
+        svm.refund(&state, &latecomer).unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+
+        // with the event authority passed, a program wide unpause is a CPI event
+        let unpause = client::with_event_authority(client::set_pause(
+            &guardian.pubkey(),
+            None,
+            SetPause {
+                paused: 0,
+                reason: 4,
+            },
+        ));
+        let tx = svm.send(&[unpause.clone()], &guardian, &[]).unwrap();
+        assert!(matches!(
+            cpi_events(&[unpause], &guardian.pubkey(), &tx)[..],
+            [FundraiserEvent::Unpaused(Unpaused {
+                fundraiser: [0; 32],
+                reason: 4,
+                ..
+            })]
+        ));
+    }
+
+    #[test]
+    pub fn test_set_guardian() {
+        let (mut svm, state) = setup();
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let guardian = Keypair::new();
+        let new_guardian = Keypair::new();
+        svm.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&new_guardian.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        svm.set_upgrade_authority(&guardian.pubkey());
+        initialize_config(&mut svm, &guardian).unwrap();
+
+        // the config can't be created twice
+        assert!(initialize_config(&mut svm, &guardian).is_err());
+
+        // only the guardian can hand the role over
+        let usurp = client::set_guardian(&state.maker.pubkey(), &new_guardian.pubkey());
+        assert!(svm.send(&[usurp], &state.maker, &[&new_guardian]).is_err());
+
+        let handoff = client::set_guardian(&guardian.pubkey(), &new_guardian.pubkey());
+        svm.send(&[handoff], &guardian, &[&new_guardian]).unwrap();
+
+        let config = svm.get_account(&client::config_pda()).unwrap();
+        let config = bytemuck::try_from_bytes::<crate::state::Config>(&config.data).unwrap();
+        assert_eq!(config.guardian, new_guardian.pubkey().to_bytes());
+
+        // the old guardian is out, the new one is in
+        assert!(set_pause(&mut svm, &guardian, Some(state.fundraiser.0), true, 1).is_err());
+        set_pause(&mut svm, &new_guardian, Some(state.fundraiser.0), true, 1).unwrap();
+    }
+
+    #[test]
+    pub fn test_authority_handoff() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        let new_authority = Keypair::new();
+        svm.airdrop(&new_authority.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
 
-        let maker_deserialized_ata =
+        svm.send(
+            &[client::propose_authority(
+                &state.maker.pubkey(),
+                &state.maker.pubkey(),
+                ProposeAuthority {
+                    new_authority: new_authority.pubkey().to_bytes(),
+                },
+            )],
+            &state.maker,
+            &[],
+        )
+        .unwrap();
+
+        // a proposal alone hands over nothing
+        assert!(svm
+            .send(
+                &[client::extend(
+                    &new_authority.pubkey(),
+                    &state.maker.pubkey(),
+                    Extend {
+                        duration: 200_000_000
+                    }
+                )],
+                &new_authority,
+                &[]
+            )
+            .is_err());
+        // and only the proposed key can accept
+        assert!(svm
+            .send(
+                &[client::accept_authority(
+                    &state.maker.pubkey(),
+                    &state.maker.pubkey()
+                )],
+                &state.maker,
+                &[]
+            )
+            .is_err());
+
+        svm.send(
+            &[client::accept_authority(
+                &new_authority.pubkey(),
+                &state.maker.pubkey(),
+            )],
+            &new_authority,
+            &[],
+        )
+        .unwrap();
+
+        // the maker is now just the pda seed
+        assert!(svm.cancel(&state).is_err());
+
+        svm.send(
+            &[client::extend(
+                &new_authority.pubkey(),
+                &state.maker.pubkey(),
+                Extend {
+                    duration: 200_000_000,
+                },
+            )],
+            &new_authority,
+            &[],
+        )
+        .unwrap();
+        svm.send(
+            &[client::cancel(
+                &new_authority.pubkey(),
+                &state.maker.pubkey(),
+            )],
+            &new_authority,
+            &[],
+        )
+        .unwrap();
+
+        let fundraiser_state = svm.get_account(&state.fundraiser.0).unwrap();
+        let fundraiser_state =
             bytemuck::try_from_bytes::<crate::state::Fundraiser>(&fundraiser_state.data).unwrap();
+        assert_eq!(fundraiser_state.maker, state.maker.pubkey().to_bytes());
+        assert_eq!(
+            fundraiser_state.authority,
+            new_authority.pubkey().to_bytes()
+        );
+        assert_eq!(u64::from_le_bytes(fundraiser_state.duration), 200_000_000);
+
+        // a cancelled campaign refunds right away
+        svm.refund(&state, &contributor).unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_cosigned_admin_actions() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let cosigners = [Keypair::new(), Keypair::new(), Keypair::new()];
+        let mut cosigner_keys = [[0u8; 32]; crate::state::MAX_COSIGNERS];
+        for (key, cosigner) in cosigner_keys.iter_mut().zip(cosigners.iter()) {
+            *key = cosigner.pubkey().to_bytes();
+        }
+
+        // 2 of 3 from now on
+        svm.send(
+            &[client::set_cosigners(
+                &state.maker.pubkey(),
+                &state.maker.pubkey(),
+                SetCosigners {
+                    threshold: 2,
+                    cosigner_count: 3,
+                    cosigners: cosigner_keys,
+                },
+            )],
+            &state.maker,
+            &[],
+        )
+        .unwrap();
+
+        let extend = Extend {
+            duration: 200_000_000,
+        };
+
+        assert!(svm
+            .send(
+                &[client::extend(
+                    &state.maker.pubkey(),
+                    &state.maker.pubkey(),
+                    extend
+                )],
+                &state.maker,
+                &[]
+            )
+            .is_err());
+        assert!(send_cosigned(
+            &mut svm,
+            client::extend(&state.maker.pubkey(), &state.maker.pubkey(), extend),
+            &state.maker,
+            &[&cosigners[0]]
+        )
+        .is_err());
+        // the same cosigner twice still counts once
+        assert!(send_cosigned(
+            &mut svm,
+            client::extend(&state.maker.pubkey(), &state.maker.pubkey(), extend),
+            &state.maker,
+            &[&cosigners[0], &cosigners[0]]
+        )
+        .is_err());
+
+        send_cosigned(
+            &mut svm,
+            client::extend(&state.maker.pubkey(), &state.maker.pubkey(), extend),
+            &state.maker,
+            &[&cosigners[0], &cosigners[2]],
+        )
+        .unwrap();
+
+        // the set can't be swapped out without meeting the threshold either
+        let takeover = SetCosigners {
+            threshold: 0,
+            cosigner_count: 0,
+            cosigners: [[0u8; 32]; crate::state::MAX_COSIGNERS],
+        };
+        assert!(svm
+            .send(
+                &[client::set_cosigners(
+                    &state.maker.pubkey(),
+                    &state.maker.pubkey(),
+                    takeover
+                )],
+                &state.maker,
+                &[]
+            )
+            .is_err());
+        send_cosigned(
+            &mut svm,
+            client::set_cosigners(&state.maker.pubkey(), &state.maker.pubkey(), takeover),
+            &state.maker,
+            &[&cosigners[1], &cosigners[2]],
+        )
+        .unwrap();
+
+        svm.cancel(&state).unwrap();
+    }
+
+    #[test]
+    pub fn test_timelocked_claim() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 20_000_000,
+                duration: 3_600,
+                claim_timelock: 600,
+                arbiter: [0u8; 32],
+            },
+        )
+        .unwrap();
+        contribute(&mut svm, &state).unwrap();
+        contribute(&mut svm, &state).unwrap();
+
+        // a timelocked raise can't be claimed directly
+        assert!(svm.claim(&state).is_err());
+        // nor executed before it was requested
+        assert!(svm.execute_claim(&state).is_err());
+
+        svm.request_claim(&state).unwrap();
+        let requested_at = svm.get_sysvar::<Clock>().unix_timestamp;
+
+        svm.warp_to(requested_at + 599);
+        assert!(svm.execute_claim(&state).is_err());
+
+        // nobody objected, so it pays out once the window closes
+        svm.warp_to(requested_at + 600);
+        svm.execute_claim(&state).unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+        assert_eq!(svm.token_balance(&state.maker_ata), 1_020_000_000);
+    }
+
+    #[test]
+    pub fn test_contributors_block_claim() {
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 600,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributors = [
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+        ];
+
+        // no window, no objections
+        assert!(object_claim(&mut svm, &state, &contributors[0]).is_err());
+
+        svm.request_claim(&state).unwrap();
+        let requested_at = svm.get_sysvar::<Clock>().unix_timestamp;
+
+        // a third of the raise is not enough to block
+        object_claim(&mut svm, &state, &contributors[0]).unwrap();
+        assert!(object_claim(&mut svm, &state, &contributors[0]).is_err());
+        // strangers don't get a vote
+        let stranger = svm.fund_contributor(&state);
+        assert!(object_claim(&mut svm, &state, &stranger).is_err());
 
-        let vault_in_program = svm.get_account(&state.vault).unwrap();
-        let vault_as_account =
-            litesvm_token::spl_token::state::Account::unpack(&vault_in_program.data).unwrap();
+        // two thirds is
+        object_claim(&mut svm, &state, &contributors[1]).unwrap();
 
-        msg!("new vault balance: {:#?}", vault_as_account.amount);
-        msg!(
-            "new user token bump: {:#?}",
-            maker_deserialized_ata.amount_to_raise
+        svm.warp_to(requested_at + 600);
+        assert!(svm.execute_claim(&state).is_err());
+
+        // and backers get their money back
+        for contributor in contributors.iter() {
+            svm.refund(&state, contributor).unwrap();
+        }
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_arbiter_splits_dispute() {
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributors = [
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+        ];
+
+        // strangers can't open a dispute, contributors can
+        let stranger = svm.fund_contributor(&state);
+        assert!(open_dispute(&mut svm, &state, &stranger, 1).is_err());
+        open_dispute(&mut svm, &state, &contributors[0], 1).unwrap();
+
+        let dispute = svm
+            .get_account(&client::dispute_pda(&state.fundraiser.0))
+            .unwrap();
+        let dispute_state = bytemuck::from_bytes::<crate::state::Dispute>(&dispute.data);
+        assert_eq!(dispute_state.opened_by, contributors[0].pubkey().to_bytes());
+        assert_eq!(dispute_state.reason, [1]);
+
+        // one dispute at a time, and it freezes payouts
+        assert!(open_dispute(&mut svm, &state, &state.maker, 2).is_err());
+        assert!(svm.claim(&state).is_err());
+
+        // only the arbiter rules, and a split needs two sides
+        let split = ResolveDispute {
+            resolution: crate::state::Resolution::Split as u8,
+            maker_share_bps: 2_500u16.to_le_bytes(),
+        };
+        assert!(resolve_dispute(&mut svm, &state, &stranger, split).is_err());
+        assert!(resolve_dispute(
+            &mut svm,
+            &state,
+            &arbiter,
+            ResolveDispute {
+                maker_share_bps: 10_000u16.to_le_bytes(),
+                ..split
+            }
+        )
+        .is_err());
+        resolve_dispute(&mut svm, &state, &arbiter, split).unwrap();
+        assert!(resolve_dispute(&mut svm, &state, &arbiter, split).is_err());
+
+        // a quarter to the maker, the rest back to contributors
+        let wallets: Vec<Pubkey> = contributors.iter().map(Keypair::pubkey).collect();
+        assert_eq!(svm.token_balance(&state.maker_ata), 1_007_500_000);
+        svm.assert_invariants(&state, &wallets);
+        assert!(svm.claim(&state).is_err());
+        for contributor in contributors.iter() {
+            svm.refund(&state, contributor).unwrap();
+            svm.assert_invariants(&state, &wallets);
+            let contributor_ata = spl_associated_token_account::get_associated_token_address(
+                &contributor.pubkey(),
+                &state.mint,
+            );
+            assert_eq!(svm.token_balance(&contributor_ata), 997_500_000);
+        }
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_arbiter_releases_dispute() {
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let mut contributors = vec![contribute(&mut svm, &state).unwrap()];
+
+        open_dispute(&mut svm, &state, &state.maker, 7).unwrap();
+        assert!(svm.refund(&state, &contributors[0]).is_err());
+
+        let release = ResolveDispute {
+            resolution: crate::state::Resolution::Release as u8,
+            maker_share_bps: [0; 2],
+        };
+
+        // a release can't pay out a raise short of its target
+        assert!(resolve_dispute(&mut svm, &state, &arbiter, release).is_err());
+
+        // once backers make up the target it can
+        contributors.push(svm.fund_contributor(&state));
+        contributors.push(svm.fund_contributor(&state));
+        for contributor in &contributors[1..] {
+            svm.contribute(&state, contributor, 10_000_000).unwrap();
+        }
+        resolve_dispute(&mut svm, &state, &arbiter, release).unwrap();
+        assert_eq!(svm.token_balance(&state.maker_ata), 1_030_000_000);
+
+        // released raises are final
+        svm.warp_past_deadline(&state);
+        for contributor in &contributors {
+            assert!(svm.refund(&state, contributor).is_err());
+        }
+    }
+
+    #[test]
+    pub fn test_dispute_lapses() {
+        use crate::events::{Cancelled, DisputeResolved, FundraiserEvent, Refunded};
+        use crate::state::{Resolution, DISPUTE_TIMEOUT};
+
+        let mut env = FundraiserTestEnv::new();
+        let arbiter = Keypair::new();
+        let (campaign, contributors) = env.campaign_with_contributors(
+            InitializeFundraiser {
+                arbiter: arbiter.pubkey().to_bytes(),
+                ..hour_long(30_000_000)
+            },
+            &[10_000_000; 3],
         );
+        let opened_at = env.now();
+        open_dispute(&mut env, &campaign, &contributors[0], 1).unwrap();
+
+        // refunds stay frozen while the arbiter can still rule
+        env.warp_to(opened_at + DISPUTE_TIMEOUT - 1);
+        assert!(env.refund(&campaign, &contributors[0]).is_err());
+
+        // an arbiter that stayed away can't rule afterwards, and the first
+        // refund cancels the campaign
+        env.warp_to(opened_at + DISPUTE_TIMEOUT);
+        let refund_all = ResolveDispute {
+            resolution: Resolution::Refund as u8,
+            maker_share_bps: [0; 2],
+        };
+        assert!(resolve_dispute(&mut env, &campaign, &arbiter, refund_all).is_err());
+
+        let tx = env.refund(&campaign, &contributors[0]).unwrap();
+        let fundraiser = campaign.fundraiser.0.to_bytes();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![
+                FundraiserEvent::DisputeResolved(DisputeResolved {
+                    fundraiser,
+                    arbiter: [0; 32],
+                    resolution: Resolution::Refund as u8,
+                    maker_share_bps: [0; 2],
+                }),
+                FundraiserEvent::Cancelled(Cancelled {
+                    fundraiser,
+                    authority: [0; 32],
+                }),
+                FundraiserEvent::Refunded(Refunded {
+                    fundraiser,
+                    contributor: contributors[0].pubkey().to_bytes(),
+                    amount: 10_000_000,
+                    current_amount: 20_000_000,
+                }),
+            ]
+        );
+        let state = env.fundraiser(&campaign);
+        assert!(state.is_cancelled() && !state.is_disputed());
+
+        assert!(env.claim(&campaign).is_err());
+        for contributor in &contributors[1..] {
+            env.refund(&campaign, contributor).unwrap();
+        }
+        assert_eq!(env.token_balance(&campaign.vault), 0);
+    }
+
+    #[test]
+    pub fn test_paused_release() {
+        use crate::events::{Claimed, DisputeResolved, FundraiserEvent};
+
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        for _ in 0..3 {
+            let contributor = svm.fund_contributor(&state);
+            svm.contribute(&state, &contributor, 10_000_000).unwrap();
+        }
+        open_dispute(&mut svm, &state, &state.maker, 7).unwrap();
+
+        let guardian = Keypair::new();
+        svm.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        svm.set_upgrade_authority(&guardian.pubkey());
+        initialize_config(&mut svm, &guardian).unwrap();
+
+        let release = ResolveDispute {
+            resolution: crate::state::Resolution::Release as u8,
+            maker_share_bps: [0; 2],
+        };
+
+        // the ruling can't pay out while either the campaign or the program is paused
+        for fundraiser in [Some(state.fundraiser.0), None] {
+            set_pause(&mut svm, &guardian, fundraiser, true, 1).unwrap();
+            assert!(resolve_dispute(&mut svm, &state, &arbiter, release).is_err());
+            set_pause(&mut svm, &guardian, fundraiser, false, 0).unwrap();
+        }
+        assert_eq!(svm.token_balance(&state.vault), 30_000_000);
+
+        let tx = resolve_dispute(&mut svm, &state, &arbiter, release).unwrap();
+        let fundraiser = state.fundraiser.0.to_bytes();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![
+                FundraiserEvent::DisputeResolved(DisputeResolved {
+                    fundraiser,
+                    arbiter: arbiter.pubkey().to_bytes(),
+                    resolution: crate::state::Resolution::Release as u8,
+                    maker_share_bps: 10_000u16.to_le_bytes(),
+                }),
+                FundraiserEvent::Claimed(Claimed {
+                    fundraiser,
+                    authority: state.maker.pubkey().to_bytes(),
+                    amount: 30_000_000,
+                }),
+            ]
+        );
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_no_dispute_once_refundable() {
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        // the target was missed, so a dispute can't hold refunds back
+        svm.warp_past_deadline(&state);
+        assert!(open_dispute(&mut svm, &state, &state.maker, 1).is_err());
+        assert!(open_dispute(&mut svm, &state, &contributor, 1).is_err());
+
+        svm.refund(&state, &contributor).unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_prefunded_dispute() {
+        let (mut svm, state) = setup();
+
+        let arbiter = Keypair::new();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        // the dispute address is known as soon as the campaign is
+        let dispute = client::dispute_pda(&state.fundraiser.0);
+        svm.airdrop(&dispute, 1_000_000).unwrap();
+
+        open_dispute(&mut svm, &state, &contributor, 1).unwrap();
+        assert_eq!(svm.get_account(&dispute).unwrap().owner, client::PROGRAM_ID);
+        assert!(svm.fundraiser(&state).is_disputed());
+    }
+
+    #[test]
+    pub fn test_campaign_metadata() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let load = |svm: &FundraiserTestEnv| {
+            let account = svm
+                .get_account(&client::metadata_pda(&state.fundraiser.0))
+                .unwrap();
+            *bytemuck::from_bytes::<crate::state::CampaignMetadata>(&account.data)
+        };
+        let written = load(&svm);
+        assert_eq!(written.fundraiser, state.fundraiser.0.to_bytes());
+        assert_eq!(written.title(), "Clean water");
+        assert_eq!(written.uri(), "https://example.com/campaign.json");
+        assert_eq!(written.category, [1]);
+
+        // unknown categories and hidden bytes past the title are rejected
+        assert!(update_metadata(&mut svm, &state, metadata("Clean water", 8)).is_err());
+        let mut padded = metadata("Clean water", 1);
+        padded.title[40] = b'!';
+        assert!(update_metadata(&mut svm, &state, padded).is_err());
+
+        update_metadata(&mut svm, &state, metadata("Clean water for Lagos", 3)).unwrap();
+        assert_eq!(load(&svm).title(), "Clean water for Lagos");
+        assert_eq!(load(&svm).category, [3]);
+
+        // frozen once backers are in
+        contribute(&mut svm, &state).unwrap();
+        assert!(update_metadata(&mut svm, &state, metadata("Something else", 3)).is_err());
+        assert_eq!(load(&svm).title(), "Clean water for Lagos");
+    }
+
+    #[test]
+    pub fn test_campaign_registry() {
+        let (mut svm, state) = setup();
+
+        create_fundraiser(&mut svm, &state).unwrap();
+        // lamports sent ahead to the page the last campaign rolls over to
+        svm.airdrop(&client::registry_page_pda(1), LAMPORTS_PER_SOL)
+            .unwrap();
+        let mut makers = vec![state];
+        for _ in 0..crate::state::REGISTRY_PAGE_CAPACITY {
+            let maker = svm.new_maker();
+            create_fundraiser(&mut svm, &maker).unwrap();
+            makers.push(maker);
+        }
+
+        // campaigns fill pages in creation order, then roll over to the next
+        let first_page = load_registry_page(&svm, 0);
+        let second_page = load_registry_page(&svm, 1);
+        assert_eq!(
+            first_page.entries().len(),
+            crate::state::REGISTRY_PAGE_CAPACITY
+        );
+        assert_eq!(second_page.index, 1u64.to_le_bytes());
+        assert_eq!(second_page.entries().len(), 1);
+        let registered: Vec<[u8; 32]> = first_page
+            .entries()
+            .iter()
+            .chain(second_page.entries())
+            .map(|entry| entry.fundraiser)
+            .collect();
+        let created: Vec<[u8; 32]> = makers
+            .iter()
+            .map(|maker| maker.fundraiser.0.to_bytes())
+            .collect();
+        assert_eq!(registered, created);
+        assert!(first_page
+            .entries()
+            .iter()
+            .all(|entry| entry.category == [1]));
+
+        // running campaigns stay listed
+        assert!(delist(&mut svm, &makers[1], 1).is_err());
+
+        svm.cancel(&makers[1]).unwrap();
+        // the campaign has to be looked up on its own page
+        assert!(delist(
+            &mut svm,
+            &makers[1],
+            crate::state::REGISTRY_PAGE_CAPACITY as u64 + 1
+        )
+        .is_err());
+        delist(&mut svm, &makers[1], 1).unwrap();
+        assert!(delist(&mut svm, &makers[1], 1).is_err());
+
+        let first_page = load_registry_page(&svm, 0);
+        assert!(first_page.entries()[1].is_closed());
+        assert_eq!(
+            first_page
+                .entries()
+                .iter()
+                .filter(|entry| entry.is_closed())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    pub fn test_prefunded_registry() {
+        let mut env = FundraiserTestEnv::new();
+        // a griefer funds the registry and its first page before they exist,
+        // enough to keep them alive but short of what the program needs
+        for address in [client::registry_pda(), client::registry_page_pda(0)] {
+            env.airdrop(&address, 1_000_000).unwrap();
+        }
+
+        let campaign = env.create_campaign(hour_long(100_000_000));
+
+        assert_eq!(env.registry_count(), 1);
+        assert_eq!(
+            load_registry_page(&env, 0).entries()[0].fundraiser,
+            campaign.fundraiser.0.to_bytes()
+        );
+        for address in [client::registry_pda(), client::registry_page_pda(0)] {
+            let account = env.get_account(&address).unwrap();
+            assert_eq!(account.owner, client::PROGRAM_ID);
+            assert!(account.lamports >= env.minimum_balance_for_rent_exemption(account.data.len()));
+        }
+        env.create_campaign(hour_long(100_000_000));
+        assert_eq!(env.registry_count(), 2);
+    }
+
+    #[test]
+    pub fn test_prefunded_campaign_accounts() {
+        let mut env = FundraiserTestEnv::new();
+        let campaign = env.new_maker();
+        // every address below can be derived before the account exists
+        let fundraiser = campaign.fundraiser.0;
+        for address in [fundraiser, client::metadata_pda(&fundraiser)] {
+            env.airdrop(&address, 1_000_000).unwrap();
+        }
+        env.initialize(
+            &campaign,
+            hour_long(100_000_000),
+            metadata("Clean water", 1),
+        )
+        .unwrap();
+        assert_eq!(
+            env.get_account(&client::metadata_pda(&fundraiser))
+                .unwrap()
+                .owner,
+            client::PROGRAM_ID
+        );
+
+        let contributor = env.fund_contributor(&campaign);
+        let ledger = client::contributor_pda(&fundraiser, &contributor.pubkey());
+        env.airdrop(&ledger, 1_000_000).unwrap();
+        env.contribute(&campaign, &contributor, 10_000_000).unwrap();
+        assert_eq!(
+            env.ledger(&campaign, &contributor.pubkey()).unwrap().amount,
+            10_000_000u64.to_le_bytes()
+        );
+        env.assert_invariants(&campaign, &[contributor.pubkey()]);
+
+        let guardian = Keypair::new();
+        env.airdrop(&guardian.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        env.set_upgrade_authority(&guardian.pubkey());
+        env.airdrop(&client::config_pda(), 1_000_000).unwrap();
+        initialize_config(&mut env, &guardian).unwrap();
+        // still only once
+        assert!(initialize_config(&mut env, &guardian).is_err());
+    }
+
+    #[test]
+    pub fn test_recorded_vault_and_ledger() {
+        let mut env = FundraiserTestEnv::new();
+        let (campaign, contributors) =
+            env.campaign_with_contributors(hour_long(100_000_000), &[10_000_000, 20_000_000]);
+        assert_eq!(env.fundraiser(&campaign).vault, campaign.vault.to_bytes());
+
+        // another token account of the fundraiser's is not its vault
+        let decoy = litesvm_token::CreateAccount::new(&mut env, &campaign.maker, &campaign.mint)
+            .owner(&campaign.fundraiser.0)
+            .send()
+            .unwrap();
+        let mut instruction = client::contribute(
+            &contributors[0].pubkey(),
+            &campaign.maker.pubkey(),
+            &campaign.mint,
+            ContributeArgs { amount: 10_000_000 },
+        );
+        instruction
+            .accounts
+            .iter_mut()
+            .find(|meta| meta.pubkey == campaign.vault)
+            .unwrap()
+            .pubkey = decoy;
+        assert!(env.send(&[instruction], &contributors[0], &[]).is_err());
+
+        // nor is another wallet's ledger the contributor's
+        env.warp_past_deadline(&campaign);
+        let mut instruction = client::refund(
+            &contributors[0].pubkey(),
+            &campaign.maker.pubkey(),
+            &campaign.mint,
+        );
+        let own_ledger = client::contributor_pda(&campaign.fundraiser.0, &contributors[0].pubkey());
+        instruction
+            .accounts
+            .iter_mut()
+            .find(|meta| meta.pubkey == own_ledger)
+            .unwrap()
+            .pubkey = client::contributor_pda(&campaign.fundraiser.0, &contributors[1].pubkey());
+        assert!(env.send(&[instruction], &contributors[0], &[]).is_err());
+
+        env.refund(&campaign, &contributors[0]).unwrap();
+        let wallets: Vec<Pubkey> = contributors.iter().map(Keypair::pubkey).collect();
+        env.assert_invariants(&campaign, &wallets);
+    }
+
+    #[test]
+    pub fn test_events() {
+        use crate::events::{Cancelled, Claimed, Contributed, FundraiserEvent, Refunded};
+
+        let (mut svm, state) = setup();
+
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 20_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: [0u8; 32],
+            },
+        )
+        .unwrap();
+
+        let contributor = svm.fund_contributor(&state);
+        let tx = send_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            ContributeArgs { amount: 20_000_000 }.to_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Contributed(Contributed {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                contributor: contributor.pubkey().to_bytes(),
+                amount: 20_000_000,
+                current_amount: 20_000_000,
+            })]
+        );
+
+        let tx = svm.claim(&state).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Claimed(Claimed {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                authority: state.maker.pubkey().to_bytes(),
+                amount: 20_000_000,
+            })]
+        );
+
+        // a second campaign that gets cancelled
+        let state = svm.new_maker();
+        create_fundraiser(&mut svm, &state).unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        let tx = svm.cancel(&state).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Cancelled(Cancelled {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                authority: state.maker.pubkey().to_bytes(),
+            })]
+        );
+
+        let tx = svm.refund(&state, &contributor).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::Refunded(Refunded {
+                fundraiser: state.fundraiser.0.to_bytes(),
+                contributor: contributor.pubkey().to_bytes(),
+                amount: 10_000_000,
+                current_amount: 0,
+            })]
+        );
+
+        // anything else in the logs is skipped
+        assert!(
+            FundraiserEvent::from_logs(&["Program log: hi", "Program data: AA== !!"]).is_empty()
+        );
+    }
+
+    #[test]
+    pub fn test_admin_events() {
+        use crate::events::{
+            AuthorityAccepted, AuthorityProposed, Cancelled, ClaimObjected, CosignersSet,
+            DisputeOpened, DisputeResolved, FundraiserEvent,
+        };
+        use crate::state::{Resolution, MAX_COSIGNERS};
+
+        let (mut svm, state) = setup();
+        let fundraiser = state.fundraiser.0.to_bytes();
+        let maker = state.maker.pubkey().to_bytes();
+
+        let arbiter = Keypair::new();
+        svm.airdrop(&arbiter.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 600,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributors = [
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+            contribute(&mut svm, &state).unwrap(),
+        ];
+
+        // a cosigner on record, not yet required
+        let mut cosigners = [[0u8; 32]; MAX_COSIGNERS];
+        cosigners[0] = Keypair::new().pubkey().to_bytes();
+        let tx = svm
+            .send(
+                &[client::set_cosigners(
+                    &state.maker.pubkey(),
+                    &state.maker.pubkey(),
+                    SetCosigners {
+                        threshold: 0,
+                        cosigner_count: 1,
+                        cosigners,
+                    },
+                )],
+                &state.maker,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::CosignersSet(CosignersSet {
+                fundraiser,
+                authority: maker,
+                threshold: 0,
+                cosigner_count: 1,
+                cosigners,
+            })]
+        );
+
+        // a contributor's objection counts its contribution, the arbiter's blocks
+        svm.request_claim(&state).unwrap();
+        let tx = object_claim(&mut svm, &state, &contributors[0]).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::ClaimObjected(ClaimObjected {
+                fundraiser,
+                objector: contributors[0].pubkey().to_bytes(),
+                objections: 10_000_000u64.to_le_bytes(),
+                claim_blocked: 0,
+            })]
+        );
+        let tx = object_claim(&mut svm, &state, &arbiter).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::ClaimObjected(ClaimObjected {
+                fundraiser,
+                objector: arbiter.pubkey().to_bytes(),
+                objections: 10_000_000u64.to_le_bytes(),
+                claim_blocked: 1,
+            })]
+        );
+
+        let new_authority = Keypair::new();
+        svm.airdrop(&new_authority.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        let tx = svm
+            .send(
+                &[client::propose_authority(
+                    &state.maker.pubkey(),
+                    &state.maker.pubkey(),
+                    ProposeAuthority {
+                        new_authority: new_authority.pubkey().to_bytes(),
+                    },
+                )],
+                &state.maker,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::AuthorityProposed(AuthorityProposed {
+                fundraiser,
+                authority: maker,
+                new_authority: new_authority.pubkey().to_bytes(),
+            })]
+        );
+        let tx = svm
+            .send(
+                &[client::accept_authority(
+                    &new_authority.pubkey(),
+                    &state.maker.pubkey(),
+                )],
+                &new_authority,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::AuthorityAccepted(AuthorityAccepted {
+                fundraiser,
+                previous_authority: maker,
+                new_authority: new_authority.pubkey().to_bytes(),
+            })]
+        );
+
+        // a second campaign that goes to its arbiter
+        let state = svm.new_maker();
+        let fundraiser = state.fundraiser.0.to_bytes();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 30_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+        )
+        .unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        let tx = open_dispute(&mut svm, &state, &contributor, 3).unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![FundraiserEvent::DisputeOpened(DisputeOpened {
+                fundraiser,
+                opened_by: contributor.pubkey().to_bytes(),
+                reason: 3,
+            })]
+        );
+
+        let tx = resolve_dispute(
+            &mut svm,
+            &state,
+            &arbiter,
+            ResolveDispute {
+                resolution: Resolution::Refund as u8,
+                maker_share_bps: [0; 2],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            FundraiserEvent::from_logs(&tx.logs),
+            vec![
+                FundraiserEvent::DisputeResolved(DisputeResolved {
+                    fundraiser,
+                    arbiter: arbiter.pubkey().to_bytes(),
+                    resolution: Resolution::Refund as u8,
+                    maker_share_bps: [0; 2],
+                }),
+                FundraiserEvent::Cancelled(Cancelled {
+                    fundraiser,
+                    authority: arbiter.pubkey().to_bytes(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_event_authority_constant() {
+        use crate::events::{EVENT_AUTHORITY, EVENT_AUTHORITY_BUMP, EVENT_AUTHORITY_SEED};
+
+        let (address, bump) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &program_id());
+        assert_eq!(address.to_bytes(), EVENT_AUTHORITY);
+        assert_eq!(bump, EVENT_AUTHORITY_BUMP);
+    }
+
+    #[test]
+    pub fn test_cpi_events() {
+        use crate::events::{event_authority, Contributed, FundraiserEvent};
+
+        let (mut svm, state) = setup();
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let contributor = svm.fund_contributor(&state);
+        let contribute_ix = client::with_event_authority(client::contribute(
+            &contributor.pubkey(),
+            &state.maker.pubkey(),
+            &state.mint,
+            ContributeArgs { amount: 10_000_000 },
+        ));
+        let tx = svm
+            .send(&[contribute_ix.clone()], &contributor, &[])
+            .unwrap();
+
+        // the event is an inner instruction now, not a log line
+        let event = Contributed {
+            fundraiser: state.fundraiser.0.to_bytes(),
+            contributor: contributor.pubkey().to_bytes(),
+            amount: 10_000_000,
+            current_amount: 10_000_000,
+        };
+        assert_eq!(
+            cpi_events(&[contribute_ix], &contributor.pubkey(), &tx),
+            vec![FundraiserEvent::Contributed(event)]
+        );
+        assert!(FundraiserEvent::from_logs(&tx.logs).is_empty());
+
+        // the same data sent to another program is not an event
+        let data = [
+            vec![FundraisingInstructions::EmitEvent as u8, 1],
+            bytemuck::bytes_of(&event).to_vec(),
+        ]
+        .concat();
+        assert!(FundraiserEvent::from_instruction_data(&crate::ID, &data).is_some());
+        assert_eq!(
+            FundraiserEvent::from_instruction_data(&spl_token::ID.to_bytes(), &data),
+            None
+        );
+
+        // nobody but the program can emit events
+        let forged_ix = Instruction {
+            program_id: program_id(),
+            accounts: vec![AccountMeta::new_readonly(event_authority().0.into(), false)],
+            data: [
+                vec![FundraisingInstructions::EmitEvent as u8, 1],
+                bytemuck::bytes_of(&event).to_vec(),
+            ]
+            .concat(),
+        };
+        let message = Message::new(&[forged_ix], Some(&contributor.pubkey()));
+        let transaction = Transaction::new(&[&contributor], message, svm.latest_blockhash());
+        assert!(svm.send_transaction(transaction).is_err());
+    }
+
+    /// where `cargo build-sbf --features debug-logs --sbf-out-dir target/deploy/debug-logs`
+    /// puts the build with diagnostic logs, next to the production one at
+    /// `test_kit::PROGRAM_PATH`
+    const DEBUG_LOGS_PROGRAM_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/deploy/debug-logs/pinocchio_fundraising.so"
+    );
+
+    // ceilings well above what contribute uses, so only a real regression
+    // trips them
+    const FIRST_CONTRIBUTE_CU_CEILING: u64 = 25_000;
+    const REPEAT_CONTRIBUTE_CU_CEILING: u64 = 15_000;
+    const DEBUG_LOGS_FIRST_CONTRIBUTE_CU_CEILING: u64 = 40_000;
+    const DEBUG_LOGS_REPEAT_CONTRIBUTE_CU_CEILING: u64 = 30_000;
+    /// the most the diagnostic logs may add to a contribution
+    const DEBUG_LOGS_CU_OVERHEAD_CEILING: u64 = 15_000;
+
+    /// a first and a repeat contribution with the program built at `path`
+    fn contribute_with_program(path: &str) -> (TransactionMetadata, TransactionMetadata) {
+        let program = std::fs::read(path)
+            .unwrap_or_else(|err| panic!("reading {path}, build it first: {err}"));
+        let mut svm = FundraiserTestEnv::with_program(&program);
+        let state = svm.new_maker();
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        let contributor = svm.fund_contributor(&state);
+        let amount = ContributeArgs { amount: 10_000_000 }.to_bytes();
+
+        // the first contribution creates the ledger, later ones reuse it
+        let first = send_contribute(&mut svm, &state, &contributor, amount.clone()).unwrap();
+        let repeat = send_contribute(&mut svm, &state, &contributor, amount).unwrap();
+
+        (first, repeat)
+    }
+
+    fn diagnostic_lines(tx: &TransactionMetadata) -> usize {
+        tx.logs
+            .iter()
+            .filter(|log| log.contains("did not fail"))
+            .count()
+    }
+
+    /// Compares the production build with the one carrying `debug-logs`, which
+    /// needs both `cargo build-sbf` and
+    /// `cargo build-sbf --features debug-logs --sbf-out-dir target/deploy/debug-logs`.
+    #[test]
+    pub fn test_contribute_compute_units() {
+        let (first, repeat) = contribute_with_program(crate::test_kit::PROGRAM_PATH);
+        let (debug_first, debug_repeat) = contribute_with_program(DEBUG_LOGS_PROGRAM_PATH);
+
+        // reusing the ledger skips its creation
+        assert!(repeat.compute_units_consumed < first.compute_units_consumed);
+        assert!(debug_repeat.compute_units_consumed < debug_first.compute_units_consumed);
+
+        for (tx, ceiling, name) in [
+            (&first, FIRST_CONTRIBUTE_CU_CEILING, "first contribute"),
+            (&repeat, REPEAT_CONTRIBUTE_CU_CEILING, "repeat contribute"),
+            (
+                &debug_first,
+                DEBUG_LOGS_FIRST_CONTRIBUTE_CU_CEILING,
+                "first contribute with debug-logs",
+            ),
+            (
+                &debug_repeat,
+                DEBUG_LOGS_REPEAT_CONTRIBUTE_CU_CEILING,
+                "repeat contribute with debug-logs",
+            ),
+        ] {
+            assert!(
+                tx.compute_units_consumed <= ceiling,
+                "{name} used {} CUs",
+                tx.compute_units_consumed
+            );
+        }
+
+        // the logs cost something, but never more than the overhead ceiling
+        for (production, debug) in [(&first, &debug_first), (&repeat, &debug_repeat)] {
+            let overhead = debug
+                .compute_units_consumed
+                .checked_sub(production.compute_units_consumed)
+                .filter(|overhead| *overhead > 0)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "debug-logs used {} CUs, production {}",
+                        debug.compute_units_consumed, production.compute_units_consumed
+                    )
+                });
+            assert!(
+                overhead <= DEBUG_LOGS_CU_OVERHEAD_CEILING,
+                "debug-logs added {overhead} CUs"
+            );
+        }
+
+        assert_eq!(diagnostic_lines(&first), 0);
+        assert!(diagnostic_lines(&debug_first) > 0);
+
+        // events are not diagnostics, they stay in either way
+        for tx in [&first, &debug_first] {
+            assert_eq!(crate::events::FundraiserEvent::from_logs(&tx.logs).len(), 1);
+        }
+    }
+
+    #[test]
+    pub fn test_account_discriminators() {
+        use crate::state::{Contributor, Fundraiser, ProgramAccount};
+
+        let (mut svm, state) = setup();
+        create_fundraiser(&mut svm, &state).unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+
+        let fundraiser = svm.get_account(&state.fundraiser.0).unwrap();
+        assert_eq!(fundraiser.data[..2], [1, 2]);
+        assert!(Fundraiser::load(&fundraiser.data).is_ok());
+
+        // nothing loads as another account type
+        let contributor_pda = client::contributor_pda(&state.fundraiser.0, &contributor.pubkey());
+        let ledger = svm.get_account(&contributor_pda).unwrap();
+        assert!(Contributor::load(&ledger.data).is_ok());
+        assert!(Fundraiser::load(&ledger.data).is_err());
+        assert!(Contributor::load(&fundraiser.data[..Contributor::LEN]).is_err());
+
+        // nor does a layout version the program doesn't know
+        let mut future = fundraiser.clone();
+        future.data[1] = 3;
+        assert!(Fundraiser::load(&future.data).is_err());
+        svm.set_account(state.fundraiser.0, future).unwrap();
+        assert!(send_contribute(
+            &mut svm,
+            &state,
+            &contributor,
+            ContributeArgs { amount: 10_000_000 }.to_bytes()
+        )
+        .is_err());
+    }
+
+    /// rewrites an account the way layout version 1 stored it
+    fn downgrade_to_v1(svm: &mut FundraiserTestEnv, key: &Pubkey, v1_len: usize) {
+        let mut account = svm.get_account(key).unwrap();
+        account.data.truncate(v1_len);
+        account.data[1] = 1;
+        account.lamports = svm.minimum_balance_for_rent_exemption(v1_len);
+        svm.set_account(*key, account).unwrap();
+    }
+
+    #[test]
+    pub fn test_migrate_v1_accounts() {
+        use crate::state::{Contributor, Fundraiser, ProgramAccount};
+
+        let (mut svm, state) = setup();
+        create_fundraiser(&mut svm, &state).unwrap();
+        let contributor = contribute(&mut svm, &state).unwrap();
+        let contributor_pda = client::contributor_pda(&state.fundraiser.0, &contributor.pubkey());
+
+        downgrade_to_v1(&mut svm, &state.fundraiser.0, Fundraiser::V1_LEN);
+        downgrade_to_v1(&mut svm, &contributor_pda, Contributor::V1_LEN);
+        let amount = ContributeArgs { amount: 10_000_000 }.to_bytes();
+        assert!(send_contribute(&mut svm, &state, &contributor, amount.clone()).is_err());
+
+        // anyone can pay to migrate a ledger, its seeds say whose it is
+        let payer = svm.fund_contributor(&state);
+        let migrate_ledger = client::migrate_contributor(
+            &payer.pubkey(),
+            &state.fundraiser.0,
+            &contributor.pubkey(),
+        );
+        let mut wrong_wallet = migrate_ledger.clone();
+        // `[payer, contributor_pda, system_program, fundraiser, wallet]`
+        wrong_wallet.accounts[4].pubkey = Keypair::new().pubkey();
+        assert!(svm.send(&[wrong_wallet], &payer, &[]).is_err());
+        svm.send(&[migrate_ledger], &payer, &[]).unwrap();
+
+        let ledger = svm.get_account(&contributor_pda).unwrap();
+        assert_eq!(ledger.data.len(), Contributor::LEN);
+        assert_eq!(
+            ledger.lamports,
+            svm.minimum_balance_for_rent_exemption(Contributor::LEN)
+        );
+        let ledger_state = Contributor::load(&ledger.data).unwrap();
+        assert_eq!(ledger_state.amount, 10_000_000u64.to_le_bytes());
+        assert_eq!(ledger_state.fundraiser, state.fundraiser.0.to_bytes());
+        assert_eq!(ledger_state.wallet, contributor.pubkey().to_bytes());
+
+        // the fundraiser's vault is vouched for by its authority
+        let migrate_fundraiser = |authority: &Keypair| {
+            client::migrate_fundraiser(
+                &payer.pubkey(),
+                &authority.pubkey(),
+                &state.maker.pubkey(),
+                &state.mint,
+            )
+        };
+        assert!(svm
+            .send(&[migrate_fundraiser(&payer)], &payer, &[])
+            .is_err());
+        svm.send(&[migrate_fundraiser(&state.maker)], &payer, &[&state.maker])
+            .unwrap();
+        assert!(svm
+            .send(&[migrate_fundraiser(&state.maker)], &payer, &[&state.maker])
+            .is_err());
+
+        let fundraiser = svm.get_account(&state.fundraiser.0).unwrap();
+        let fundraiser_state = Fundraiser::load(&fundraiser.data).unwrap();
+        assert_eq!(fundraiser_state.vault, state.vault.to_bytes());
+        assert_eq!(fundraiser_state.current_amount, 10_000_000u64.to_le_bytes());
+
+        // back in business
+        send_contribute(&mut svm, &state, &contributor, amount).unwrap();
+    }
+
+    #[test]
+    pub fn test_instruction_args_length() {
+        let (mut svm, state) = setup();
+        create_fundraiser(&mut svm, &state).unwrap();
+
+        // arguments are decoded once and must be whole
+        let contributor = svm.fund_contributor(&state);
+        assert!(send_contribute(&mut svm, &state, &contributor, vec![0; 7]).is_err());
+        // an open fundraiser expects no allowlist proof after them
+        let args = ContributeArgs { amount: 10_000_000 }.to_bytes();
+        for trailing in [vec![0], AllowlistArgs { cap: 10_000_000 }.to_bytes()] {
+            let data = [args.clone(), trailing].concat();
+            assert!(send_contribute(&mut svm, &state, &contributor, data).is_err());
+        }
+        send_contribute(&mut svm, &state, &contributor, args).unwrap();
+
+        // instructions without arguments reject stray bytes
+        let mut cancel = client::cancel(&state.maker.pubkey(), &state.maker.pubkey());
+        cancel.data.push(0);
+        assert!(svm.send(&[cancel], &state.maker, &[]).is_err());
+        svm.cancel(&state).unwrap();
+    }
+
+    #[test]
+    pub fn test_client_builders() {
+        let (mut svm, state) = setup();
+
+        assert_eq!(
+            client::fundraiser_pda(&state.maker.pubkey()),
+            state.fundraiser.0
+        );
+        assert_eq!(
+            client::associated_token_address(&state.fundraiser.0, &state.mint),
+            state.vault
+        );
+
+        CreateAssociatedTokenAccount::new(&mut svm, &state.maker, &state.mint)
+            .owner(&state.fundraiser.0)
+            .send()
+            .unwrap();
+        svm.send(
+            &[client::initialize(
+                &state.maker.pubkey(),
+                &state.mint,
+                0,
+                InitializeFundraiser {
+                    amount_to_raise: 10_000_000,
+                    duration: 3_600,
+                    claim_timelock: 0,
+                    arbiter: [0u8; 32],
+                },
+                metadata("Built by the client", 0),
+            )],
+            &state.maker,
+            &[],
+        )
+        .unwrap();
+
+        let contributor = svm.fund_contributor(&state);
+        svm.send(
+            &[client::contribute(
+                &contributor.pubkey(),
+                &state.maker.pubkey(),
+                &state.mint,
+                ContributeArgs { amount: 10_000_000 },
+            )],
+            &contributor,
+            &[],
+        )
+        .unwrap();
+
+        let fundraiser = svm.get_account(&state.fundraiser.0).unwrap();
+        let fundraiser_state = client::decode_fundraiser(&fundraiser.data).unwrap();
+        assert_eq!(fundraiser_state.current_amount, 10_000_000u64.to_le_bytes());
+        let ledger = svm
+            .get_account(&client::contributor_pda(
+                &state.fundraiser.0,
+                &contributor.pubkey(),
+            ))
+            .unwrap();
+        assert_eq!(
+            client::decode_contributor(&ledger.data).unwrap().wallet,
+            contributor.pubkey().to_bytes()
+        );
+        // decoders check the account type
+        assert!(client::decode_contributor(&fundraiser.data).is_none());
+
+        // the target is met, so no refunds and the maker claims
+        assert!(svm
+            .send(
+                &[client::refund(
+                    &contributor.pubkey(),
+                    &state.maker.pubkey(),
+                    &state.mint
+                )],
+                &contributor,
+                &[]
+            )
+            .is_err());
+        svm.send(
+            &[client::claim(
+                &state.maker.pubkey(),
+                &state.maker.pubkey(),
+                &state.mint,
+            )],
+            &state.maker,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+    }
+
+    #[test]
+    pub fn test_durable_nonce_admin_actions() {
+        use spl_token::solana_program::hash::Hash;
+
+        let (mut svm, state) = setup();
+        create_fundraiser_with(
+            &mut svm,
+            &state,
+            InitializeFundraiser {
+                amount_to_raise: 10_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: [0u8; 32],
+            },
+        )
+        .unwrap();
+        contribute(&mut svm, &state).unwrap();
+
+        // 2 of 2 cosigners, who take their time
+        let cosigners = [Keypair::new(), Keypair::new()];
+        let mut cosigner_keys = [[0u8; 32]; crate::state::MAX_COSIGNERS];
+        for (key, cosigner) in cosigner_keys.iter_mut().zip(cosigners.iter()) {
+            *key = cosigner.pubkey().to_bytes();
+        }
+        svm.send(
+            &[client::set_cosigners(
+                &state.maker.pubkey(),
+                &state.maker.pubkey(),
+                SetCosigners {
+                    threshold: 2,
+                    cosigner_count: 2,
+                    cosigners: cosigner_keys,
+                },
+            )],
+            &state.maker,
+            &[],
+        )
+        .unwrap();
+        let cosigner_keys: Vec<Pubkey> = cosigners.iter().map(Keypair::pubkey).collect();
+
+        // a nonce account the maker advances
+        let nonce = Keypair::new();
+        let transaction = Transaction::new_signed_with_payer(
+            &client::create_nonce_account(
+                &state.maker.pubkey(),
+                &nonce.pubkey(),
+                &state.maker.pubkey(),
+                svm.minimum_balance_for_rent_exemption(client::NONCE_ACCOUNT_LEN),
+            ),
+            Some(&state.maker.pubkey()),
+            &[&state.maker, &nonce],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(transaction).unwrap();
+        let nonce_value = |svm: &FundraiserTestEnv| {
+            let account = svm.get_account(&nonce.pubkey()).unwrap();
+            let (authority, value) = client::decode_nonce(&account.data).unwrap();
+            assert_eq!(authority, state.maker.pubkey());
+            Hash::new_from_array(value)
+        };
+
+        // the claim is built and signed by the maker now...
+        let claim = client::with_cosigners(
+            client::claim(&state.maker.pubkey(), &state.maker.pubkey(), &state.mint),
+            &cosigner_keys,
+        );
+        let value = nonce_value(&svm);
+        let mut durable = Transaction::new_unsigned(Message::new_with_blockhash(
+            &client::with_durable_nonce(&[claim.clone()], &nonce.pubkey(), &state.maker.pubkey()),
+            Some(&state.maker.pubkey()),
+            &value,
+        ));
+        durable.partial_sign(&[&state.maker], value);
+        let blockhash = svm.latest_blockhash();
+        let mut expiring =
+            Transaction::new_unsigned(Message::new(&[claim], Some(&state.maker.pubkey())));
+        expiring.partial_sign(&[&state.maker], blockhash);
+
+        // ...and by the cosigners hours later, long after the blockhash expired
+        for _ in 0..300 {
+            svm.expire_blockhash();
+        }
+        let now = svm.get_sysvar::<Clock>().unix_timestamp;
+        svm.warp_to(now + 3 * 3_600);
+        durable.partial_sign(&[&cosigners[0], &cosigners[1]], value);
+        expiring.partial_sign(&[&cosigners[0], &cosigners[1]], blockhash);
+
+        assert!(svm.send_transaction(expiring).is_err());
+        let maker_balance = svm.token_balance(&state.maker_ata);
+        svm.send_transaction(durable.clone()).unwrap();
+        assert_eq!(svm.token_balance(&state.vault), 0);
+        assert_eq!(
+            svm.token_balance(&state.maker_ata),
+            maker_balance + 10_000_000
+        );
+
+        // using the nonce advanced it, so the claim cannot be replayed
+        assert_ne!(nonce_value(&svm), value);
+        svm.expire_blockhash();
+        assert!(svm.send_transaction(durable).is_err());
+
+        // the same nonce then carries a late-signed cancel, advanced by a
+        // nonce authority other than the fee payer
+        let other = svm.new_maker();
+        create_fundraiser(&mut svm, &other).unwrap();
+        let value = nonce_value(&svm);
+        let mut cancel = Transaction::new_unsigned(Message::new_with_blockhash(
+            &client::with_durable_nonce(
+                &[client::cancel(&other.maker.pubkey(), &other.maker.pubkey())],
+                &nonce.pubkey(),
+                &state.maker.pubkey(),
+            ),
+            Some(&other.maker.pubkey()),
+            &value,
+        ));
+        cancel.partial_sign(&[&other.maker], value);
+        for _ in 0..300 {
+            svm.expire_blockhash();
+        }
+        cancel.partial_sign(&[&state.maker], value);
+        svm.send_transaction(cancel).unwrap();
+
+        let fundraiser = svm.get_account(&other.fundraiser.0).unwrap();
+        assert!(client::decode_fundraiser(&fundraiser.data)
+            .unwrap()
+            .is_cancelled());
+    }
+
+    #[test]
+    pub fn test_kit_campaign_with_contributors() {
+        let mut env = FundraiserTestEnv::new();
+        let (campaign, contributors) = env.campaign_with_contributors(
+            InitializeFundraiser {
+                amount_to_raise: 100_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: [0u8; 32],
+            },
+            &[10_000_000, 20_000_000, 30_000_000],
+        );
+        let wallets: Vec<Pubkey> = contributors.iter().map(Keypair::pubkey).collect();
+
+        assert_eq!(
+            env.fundraiser(&campaign).current_amount,
+            60_000_000u64.to_le_bytes()
+        );
+        env.assert_invariants(&campaign, &wallets);
+
+        // short of its target, the campaign refunds once it ended
+        assert!(env.refund(&campaign, &contributors[0]).is_err());
+        env.warp_past_deadline(&campaign);
+        env.refund(&campaign, &contributors[0]).unwrap();
+        assert!(env.ledger(&campaign, &wallets[0]).is_none());
+        env.assert_invariants(&campaign, &wallets);
+    }
+
+    #[test]
+    pub fn test_kit_invariants_after_split() {
+        let mut env = FundraiserTestEnv::new();
+        let arbiter = Keypair::new();
+        let (campaign, contributors) = env.campaign_with_contributors(
+            InitializeFundraiser {
+                amount_to_raise: 100_000_000,
+                duration: 3_600,
+                claim_timelock: 0,
+                arbiter: arbiter.pubkey().to_bytes(),
+            },
+            &[10_000_001, 20_000_003, 30_000_007],
+        );
+        let wallets: Vec<Pubkey> = contributors.iter().map(Keypair::pubkey).collect();
+
+        open_dispute(&mut env, &campaign, &campaign.maker, 1).unwrap();
+        resolve_dispute(
+            &mut env,
+            &campaign,
+            &arbiter,
+            ResolveDispute {
+                resolution: crate::state::Resolution::Split as u8,
+                maker_share_bps: 3_333u16.to_le_bytes(),
+            },
+        )
+        .unwrap();
+
+        // the authority's third left the vault, rounded down, so the vault
+        // holds a little dust past what the ledgers get back
+        assert_eq!(env.token_balance(&campaign.vault), 40_002_008);
+        env.assert_invariants(&campaign, &wallets);
+        for contributor in &contributors {
+            env.refund(&campaign, contributor).unwrap();
+            env.assert_invariants(&campaign, &wallets);
+        }
+        assert_eq!(env.token_balance(&campaign.vault), 2);
+    }
+
+    /// a one hour campaign, the deadline scenarios probe either side of its end
+    fn hour_long(amount_to_raise: u64) -> InitializeFundraiser {
+        InitializeFundraiser {
+            amount_to_raise,
+            duration: 3_600,
+            claim_timelock: 0,
+            arbiter: [0u8; 32],
+        }
+    }
+
+    fn wallet_balance(env: &FundraiserTestEnv, campaign: &Campaign, wallet: &Keypair) -> u64 {
+        env.token_balance(&client::associated_token_address(
+            &wallet.pubkey(),
+            &campaign.mint,
+        ))
+    }
+
+    #[test]
+    pub fn test_contribute_at_deadline() {
+        let mut env = FundraiserTestEnv::new();
+        let campaign = env.create_campaign(hour_long(100_000_000));
+        let contributor = env.fund_contributor(&campaign);
+
+        let fundraiser = env.fundraiser(&campaign);
+        assert_eq!(
+            fundraiser.deadline(),
+            u64::from_le_bytes(fundraiser.time_started) as i64 + 3_600
+        );
+
+        // the last second before the deadline still takes contributions
+        env.warp_to_deadline(&campaign, -1);
+        env.contribute(&campaign, &contributor, 10_000_000).unwrap();
+
+        // from the deadline on the campaign has ended
+        env.warp_to_deadline(&campaign, 0);
+        assert!(env.contribute(&campaign, &contributor, 10_000_000).is_err());
+        env.warp_to_deadline(&campaign, 1);
+        assert!(env.contribute(&campaign, &contributor, 10_000_000).is_err());
+
+        assert_eq!(
+            env.fundraiser(&campaign).current_amount,
+            10_000_000u64.to_le_bytes()
+        );
+        env.assert_invariants(&campaign, &[contributor.pubkey()]);
+    }
+
+    #[test]
+    pub fn test_refund_at_deadline() {
+        let mut env = FundraiserTestEnv::new();
+        let (campaign, contributors) =
+            env.campaign_with_contributors(hour_long(100_000_000), &[10_000_000, 20_000_000]);
+        let wallets: Vec<Pubkey> = contributors.iter().map(Keypair::pubkey).collect();
+
+        // a campaign short of its target only refunds once it ended
+        env.warp_to_deadline(&campaign, -1);
+        assert!(env.refund(&campaign, &contributors[0]).is_err());
+
+        env.warp_to_deadline(&campaign, 0);
+        env.refund(&campaign, &contributors[0]).unwrap();
+        env.assert_invariants(&campaign, &wallets);
+
+        env.warp_to_deadline(&campaign, 1);
+        env.refund(&campaign, &contributors[1]).unwrap();
+        env.assert_invariants(&campaign, &wallets);
+
+        assert_eq!(env.token_balance(&campaign.vault), 0);
+        for contributor in &contributors {
+            assert_eq!(
+                wallet_balance(&env, &campaign, contributor),
+                crate::test_kit::STARTING_TOKENS
+            );
+        }
+
+        // a funded campaign never refunds, on either side of the deadline
+        let (funded, backers) =
+            env.campaign_with_contributors(hour_long(20_000_000), &[10_000_000, 10_000_000]);
+        for offset in [-1, 0, 1] {
+            env.warp_to_deadline(&funded, offset);
+            assert!(env.refund(&funded, &backers[0]).is_err());
+        }
+    }
+
+    #[test]
+    pub fn test_claim_at_deadline() {
+        let mut env = FundraiserTestEnv::new();
+
+        // a funded campaign can be claimed the second before its deadline...
+        let (early, backers) =
+            env.campaign_with_contributors(hour_long(20_000_000), &[10_000_000, 10_000_000]);
+        env.warp_to_deadline(&early, -1);
+        env.claim(&early).unwrap();
+        env.assert_invariants(
+            &early,
+            &backers.iter().map(Keypair::pubkey).collect::<Vec<_>>(),
+        );
+
+        // ...and right at it
+        let (funded, backers) =
+            env.campaign_with_contributors(hour_long(20_000_000), &[10_000_000, 10_000_000]);
+        let wallets: Vec<Pubkey> = backers.iter().map(Keypair::pubkey).collect();
+        env.warp_to_deadline(&funded, 0);
+        let maker_balance = env.token_balance(&funded.maker_ata);
+        env.claim(&funded).unwrap();
+        assert_eq!(
+            env.token_balance(&funded.maker_ata),
+            maker_balance + 20_000_000
+        );
+        env.assert_invariants(&funded, &wallets);
+        assert!(env.claim(&funded).is_err());
+
+        // one short of its target, a campaign is never claimable
+        let (short, _) =
+            env.campaign_with_contributors(hour_long(20_000_001), &[10_000_000, 10_000_000]);
+        for offset in [-1, 0, 1] {
+            env.warp_to_deadline(&short, offset);
+            assert!(env.claim(&short).is_err());
+        }
+    }
+
+    #[test]
+    pub fn test_oversubscribed_claim_at_deadline() {
+        let mut env = FundraiserTestEnv::new();
+        let state = env.new_maker();
+        create_sale(&mut env, &state, 20_000_000, 3_600, true).unwrap();
+        for _ in 0..3 {
+            contribute(&mut env, &state).unwrap();
+        }
+
+        // the total of an oversubscribed sale is only final once it ended
+        env.warp_to_deadline(&state, -1);
+        assert!(env.claim(&state).is_err());
+        env.warp_to_deadline(&state, 0);
+        env.claim(&state).unwrap();
+        assert_eq!(env.token_balance(&state.vault), 10_000_000);
+    }
+
+    #[test]
+    pub fn test_cancel_and_extend_at_deadline() {
+        let mut env = FundraiserTestEnv::new();
+
+        let (running, backers) =
+            env.campaign_with_contributors(hour_long(100_000_000), &[10_000_000]);
+        env.warp_to_deadline(&running, -1);
+        env.cancel(&running).unwrap();
+        // cancelled, it refunds before its deadline
+        env.refund(&running, &backers[0]).unwrap();
+        env.assert_invariants(&running, &[backers[0].pubkey()]);
+
+        // from the deadline on it can neither be cancelled nor extended
+        let ended = env.create_campaign(hour_long(100_000_000));
+        env.warp_to_deadline(&ended, 0);
+        assert!(env.cancel(&ended).is_err());
+
+        let maker = ended.maker.pubkey();
+        let extend = client::extend(&maker, &maker, Extend { duration: 7_200 });
+        assert!(env.send(&[extend.clone()], &ended.maker, &[]).is_err());
+
+        // a second earlier it still could
+        env.warp_to_deadline(&ended, -1);
+        env.send(&[extend], &ended.maker, &[]).unwrap();
+        assert_eq!(
+            env.fundraiser(&ended).deadline(),
+            u64::from_le_bytes(env.fundraiser(&ended).time_started) as i64 + 7_200
+        );
+    }
+
+    #[test]
+    pub fn test_idl() {
+        let idl = crate::idl::idl();
+
+        // every instruction the program processes is described
+        let instructions = idl["instructions"].as_array().unwrap();
+        assert_eq!(instructions.len(), 27);
+
+        // account flags agree with what the client builders send
+        let contribute = instructions
+            .iter()
+            .find(|instruction| instruction["name"] == "contribute")
+            .unwrap();
+        assert_eq!(contribute["discriminator"], serde_json::json!([1]));
+        assert_eq!(contribute["args"][0]["name"], "amount");
+        let maker = Keypair::new().pubkey();
+        let metas = client::contribute(
+            &Keypair::new().pubkey(),
+            &maker,
+            &maker,
+            ContributeArgs { amount: 1 },
+        )
+        .accounts;
+        let accounts = contribute["accounts"].as_array().unwrap();
+        let (named, trailing) = accounts.split_at(metas.len());
+        for (account, meta) in named.iter().zip(&metas) {
+            assert_eq!(account["signer"], meta.is_signer);
+            assert_eq!(account["writable"], meta.is_writable);
+            assert_eq!(account["optional"], false);
+        }
+
+        // followed by what only some contributions pass
+        let trailing: Vec<_> = trailing.iter().map(|account| &account["name"]).collect();
+        assert_eq!(trailing, ["instructions_sysvar", "event_authority"]);
+        assert!(accounts[metas.len()..]
+            .iter()
+            .all(|account| account["optional"] == true));
+        let args: Vec<_> = contribute["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|arg| &arg["name"])
+            .collect();
+        assert_eq!(args, ["amount", "cap", "proof"]);
+
+        // errors are the builtins, keyed by the code the runtime reports
+        let errors = idl["errors"].as_array().unwrap();
+        let not_enough_keys = errors
+            .iter()
+            .find(|error| error["name"] == "NotEnoughAccountKeys")
+            .unwrap();
+        assert_eq!(
+            not_enough_keys["code"],
+            u64::from(pinocchio::program_error::ProgramError::NotEnoughAccountKeys)
+        );
+        assert!(errors.len() > 4);
+
+        // account types leave out the discriminator byte
+        assert_eq!(idl["accounts"][0]["name"], "Fundraiser");
+        assert_eq!(idl["accounts"][0]["discriminator"], serde_json::json!([1]));
+        let fundraiser = idl["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|ty| ty["name"] == "Fundraiser")
+            .unwrap();
+        assert_eq!(fundraiser["type"]["fields"][0]["name"], "version");
+        assert_eq!(fundraiser["type"]["fields"][3]["type"], "u64");
     }
 }